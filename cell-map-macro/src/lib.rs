@@ -8,60 +8,176 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Variant};
 
 // ------------------------------------------------------------------------------------------------
 // DERIVES
 // ------------------------------------------------------------------------------------------------
 
-#[proc_macro_derive(Layer)]
+/// Derives the `Layer` trait for a fieldless enum.
+///
+/// By default each variant is assigned an index equal to its declaration order, and `FIRST` is
+/// the first-declared variant. Both can be overridden with a `#[layer(...)]` attribute on a
+/// variant:
+///
+/// - `#[layer(index = N)]` pins that variant to index `N`, which is useful for keeping a
+///   serialized map readable across versions when variants are reordered or inserted. Every
+///   variant that doesn't specify an index is assigned one of the indices left over, in
+///   declaration order. The final set of indices must be exactly `0..variant_count`.
+/// - `#[layer(first)]` designates that variant as `Layer::FIRST`, instead of whichever variant
+///   ends up at index `0`.
+///
+/// ```rust
+/// use cell_map::Layer;
+///
+/// #[derive(Layer, Clone)]
+/// enum MyLayer {
+///     #[layer(index = 2)]
+///     Roughness,
+///     #[layer(first)]
+///     Height,
+///     Gradient,
+/// }
+/// ```
+///
+/// Deriving `Layer` on an enum with no variants, or with a variant that carries data, is a
+/// compile error.
+#[proc_macro_derive(Layer, attributes(layer))]
 pub fn derive_layer(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     // Check input is an enum
     let variants = match input.data {
-        syn::Data::Enum(e) => e.variants,
+        Data::Enum(e) => e.variants,
         _ => panic!("Layer can only be derived on enums"),
     };
 
     // Get the type name
     let name = &input.ident;
 
-    // Map the varients into the match patterns we need for the to_index function
-    let var_to_index_patterns = variants.iter().enumerate().map(|(i, v)| {
-        let var_name = &v.ident;
-
-        quote! {
-            #name::#var_name => #i
+    if variants.is_empty() {
+        return syn::Error::new_spanned(
+            name,
+            "Layer cannot be derived for an enum with no variants",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    for variant in &variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                variant,
+                "Layer can only be derived for enums whose variants don't carry data",
+            )
+            .to_compile_error()
+            .into();
         }
-    });
+    }
 
-    // Map the varients into the match patterns we need for the from_index function
-    let var_from_index_patterns = variants.iter().enumerate().map(|(i, v)| {
-        let var_name = &v.ident;
+    let num_variants = variants.len();
 
-        quote! {
-            #i => #name::#var_name
+    let mut parsed = Vec::with_capacity(num_variants);
+    for variant in &variants {
+        let (explicit_index, is_first) = match parse_layer_attr(variant) {
+            Ok(parsed) => parsed,
+            Err(e) => return e.to_compile_error().into(),
+        };
+        parsed.push((variant, explicit_index, is_first));
+    }
+
+    // Variants with an explicit `#[layer(index = N)]` keep it; the rest are assigned whatever
+    // indices are left over, in declaration order. The final set of indices must be exactly
+    // `0..num_variants`, since `to_index()` is used directly as a storage offset by `CellMap`.
+    let mut taken = vec![false; num_variants];
+    for (variant, explicit_index, _) in &parsed {
+        if let Some(index) = explicit_index {
+            if *index >= num_variants || taken[*index] {
+                return syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "explicit layer index {} is out of range or already used; indices must \
+                         form a permutation of 0..{}",
+                        index, num_variants
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+            taken[*index] = true;
         }
-    });
-
-    let var_all_patterns = variants.iter().map(|v| {
-        let var_name = &v.ident;
-
-        quote! {
-            #name::#var_name
+    }
+
+    let mut next_free = 0;
+    let indices: Vec<usize> = parsed
+        .iter()
+        .map(|(_, explicit_index, _)| match explicit_index {
+            Some(index) => *index,
+            None => {
+                while taken[next_free] {
+                    next_free += 1;
+                }
+                taken[next_free] = true;
+                next_free
+            }
+        })
+        .collect();
+
+    // FIRST is whichever variant is marked `#[layer(first)]`, or whichever ended up at index 0
+    // if none are marked.
+    let first_markers: Vec<usize> = parsed
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (_, _, is_first))| is_first.then_some(i))
+        .collect();
+
+    let first_var_ident: &Ident = match first_markers.as_slice() {
+        [] => {
+            let i = indices
+                .iter()
+                .position(|&index| index == 0)
+                .expect("index 0 is always assigned to exactly one variant");
+            &parsed[i].0.ident
         }
+        [i] => &parsed[*i].0.ident,
+        [_, second, ..] => {
+            return syn::Error::new_spanned(
+                parsed[*second].0,
+                "only one variant may be marked `#[layer(first)]`",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // Map the variants into the match patterns we need for the to_index function
+    let var_to_index_patterns = parsed.iter().zip(&indices).map(|((variant, ..), index)| {
+        let var_name = &variant.ident;
+        quote! { #name::#var_name => #index }
     });
 
-    let first_var_name = &variants[0].ident;
+    // Map the variants into the match patterns we need for the try_from_index function
+    let var_from_index_patterns = parsed.iter().zip(&indices).map(|((variant, ..), index)| {
+        let var_name = &variant.ident;
+        quote! { #index => ::core::option::Option::Some(#name::#var_name) }
+    });
 
-    let num_variants = variants.len();
+    // Build the `all()` array in index order, so it matches to_index()'s ordering regardless of
+    // declaration order.
+    let mut by_index: Vec<Option<&Ident>> = vec![None; num_variants];
+    for ((variant, ..), &index) in parsed.iter().zip(&indices) {
+        by_index[index] = Some(&variant.ident);
+    }
+    let var_all_patterns = by_index.into_iter().map(|var_name| {
+        let var_name = var_name.expect("every index in 0..num_variants is assigned exactly once");
+        quote! { #name::#var_name }
+    });
 
     let impled = quote! {
         impl ::cell_map::Layer for #name {
             const NUM_LAYERS: usize = #num_variants;
 
-            const FIRST: Self = Self::#first_var_name;
+            const FIRST: Self = Self::#first_var_ident;
 
             fn to_index(&self) -> usize {
                 match self {
@@ -70,17 +186,59 @@ pub fn derive_layer(input: TokenStream) -> TokenStream {
             }
 
             fn from_index(index: usize) -> Self {
+                <Self as ::cell_map::Layer>::try_from_index(index).unwrap_or_else(|| {
+                    panic!(
+                        "Got a layer index of {} but there are only {} layers",
+                        index, #num_variants
+                    )
+                })
+            }
+
+            fn try_from_index(index: usize) -> ::core::option::Option<Self> {
                 match index {
-                    #(#var_from_index_patterns),*,
-                    _ => panic!("Got a layer index of {} but there are only {} layers", index, #num_variants)
+                    #(#var_from_index_patterns,)*
+                    _ => ::core::option::Option::None,
                 }
             }
 
-            fn all() -> Vec<Self> {
-                vec![#(#var_all_patterns),*]
+            fn all() -> ::std::vec::Vec<Self> {
+                // The array itself is built in a const context, since the number and identity of
+                // layers is known at compile time; it's converted to a Vec here only to match
+                // `Layer::all()`'s signature, which has to stay `Vec`-returning so that
+                // runtime-sized `Layer` impls like `DynamicLayer` can implement it too.
+                const ALL: [#name; #num_variants] = [#(#var_all_patterns),*];
+                ALL.to_vec()
             }
         }
     };
 
     impled.into()
 }
+
+/// Parses a variant's `#[layer(...)]` attribute (if any) into its explicit index and whether it's
+/// marked as `FIRST`.
+fn parse_layer_attr(variant: &Variant) -> syn::Result<(Option<usize>, bool)> {
+    let mut explicit_index = None;
+    let mut is_first = false;
+
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("layer") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("index") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                explicit_index = Some(lit.base10_parse()?);
+                Ok(())
+            } else if meta.path.is_ident("first") {
+                is_first = true;
+                Ok(())
+            } else {
+                Err(meta.error("unrecognised `layer` attribute, expected `index = N` or `first`"))
+            }
+        })?;
+    }
+
+    Ok((explicit_index, is_first))
+}
@@ -0,0 +1,14 @@
+//! Test that the Layer derive rejects data-carrying variants and empty enums
+
+use cell_map_macro::Layer;
+
+#[derive(Layer, Clone)]
+pub enum DataCarrying {
+    Height(f64),
+    Gradient,
+}
+
+#[derive(Layer, Clone)]
+pub enum Empty {}
+
+fn main() {}
@@ -1,9 +1,69 @@
-//! Test that the Layer trait can be derived for enums
+//! Test that the Layer trait can be derived for enums, and that the derived impl assigns
+//! indices, `FIRST`, and `try_from_index` the way the `#[layer(...)]` attributes describe.
 
+use cell_map::Layer as _;
 use cell_map_macro::Layer;
 
-#[derive(Layer, Clone)]
+#[derive(Layer, Clone, Debug, PartialEq)]
 pub enum MyLayer {
     Height,
     Gradient,
 }
+
+#[derive(Layer, Clone, Debug, PartialEq)]
+pub enum MyPinnedLayer {
+    #[layer(index = 2)]
+    Roughness,
+    #[layer(first)]
+    Height,
+    Gradient,
+}
+
+/// A layer set where the `#[layer(first)]` variant doesn't end up at index 0, so `FIRST` and
+/// "whichever variant is index 0" can be told apart.
+#[derive(Layer, Clone, Debug, PartialEq)]
+pub enum MyDecoupledFirstLayer {
+    Zero,
+    #[layer(first)]
+    One,
+}
+
+fn main() {
+    // Default indices follow declaration order, and FIRST defaults to whichever variant is
+    // index 0.
+    assert_eq!(MyLayer::NUM_LAYERS, 2);
+    assert_eq!(MyLayer::FIRST, MyLayer::Height);
+    assert_eq!(MyLayer::Height.to_index(), 0);
+    assert_eq!(MyLayer::Gradient.to_index(), 1);
+    assert_eq!(MyLayer::from_index(0), MyLayer::Height);
+    assert_eq!(MyLayer::from_index(1), MyLayer::Gradient);
+    assert_eq!(MyLayer::try_from_index(0), Some(MyLayer::Height));
+    assert_eq!(MyLayer::try_from_index(2), None);
+    assert_eq!(MyLayer::all(), vec![MyLayer::Height, MyLayer::Gradient]);
+
+    // An explicit `#[layer(index = 2)]` pins that variant's index; the remaining variants take
+    // whatever indices are left over, in declaration order.
+    assert_eq!(MyPinnedLayer::NUM_LAYERS, 3);
+    assert_eq!(MyPinnedLayer::Roughness.to_index(), 2);
+    assert_eq!(MyPinnedLayer::Height.to_index(), 0);
+    assert_eq!(MyPinnedLayer::Gradient.to_index(), 1);
+    assert_eq!(MyPinnedLayer::FIRST, MyPinnedLayer::Height);
+    assert_eq!(
+        MyPinnedLayer::try_from_index(2),
+        Some(MyPinnedLayer::Roughness)
+    );
+    assert_eq!(MyPinnedLayer::try_from_index(3), None);
+    assert_eq!(
+        MyPinnedLayer::all(),
+        vec![
+            MyPinnedLayer::Height,
+            MyPinnedLayer::Gradient,
+            MyPinnedLayer::Roughness
+        ]
+    );
+
+    // `#[layer(first)]` picks FIRST directly, regardless of which index that variant lands on.
+    assert_eq!(MyDecoupledFirstLayer::Zero.to_index(), 0);
+    assert_eq!(MyDecoupledFirstLayer::One.to_index(), 1);
+    assert_eq!(MyDecoupledFirstLayer::FIRST, MyDecoupledFirstLayer::One);
+}
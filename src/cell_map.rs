@@ -6,12 +6,12 @@
 
 use std::{
     marker::PhantomData,
-    ops::{Index, IndexMut},
+    ops::{Bound, Index, IndexMut},
     usize,
 };
 
-use nalgebra::{Affine2, Point2, Vector2};
-use ndarray::{s, Array2};
+use nalgebra::{Affine2, Point2, Point3, Vector2, Vector3};
+use ndarray::{s, Array2, ArrayView2};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
@@ -19,10 +19,13 @@ use crate::{
     extensions::Point2Ext,
     iterators::{
         layerers::Many,
-        slicers::{Cells, Line, Windows},
+        slicers::{
+            Cells, Disk, DiskMetric, Line, PaddedWindows, Polygon, SubGrid, ThickLine, Wavefront,
+            WavefrontConnectivity, WindowPadding, Windows,
+        },
         CellMapIter, CellMapIterMut,
     },
-    map_metadata::CellMapMetadata,
+    map_metadata::{BresenhamLineIter, CellMapMetadata, LineTraversal},
     Error, Layer,
 };
 
@@ -113,7 +116,7 @@ pub struct CellMapParams {
 ///  - $x_0 <= x < x_1$
 ///  - $y_0 <= y < y_1$
 // NOTE: Range isn't uses since it's not Copy
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Bounds {
     /// The bounds on the x axis, in the format (min, max),
     pub x: (isize, isize),
@@ -158,6 +161,65 @@ where
         })
     }
 
+    /// Creates a new [`CellMap`] from the given params, filling each cell by calling `f` with its
+    /// layer and cell index.
+    ///
+    /// This avoids the intermediate allocation of constructing with [`CellMap::new`] or
+    /// [`CellMap::new_from_elem`] and then looping over [`CellMap::set`], and unlike those
+    /// constructors doesn't require `T: Default + Clone`, since every cell is produced directly
+    /// by `f`. Useful for initialising gradients, procedural terrain, or analytic potential
+    /// fields in one call.
+    pub fn new_from_fn<F>(params: CellMapParams, mut f: F) -> Self
+    where
+        F: FnMut(L, Point2<usize>) -> T,
+    {
+        let shape = params.cell_bounds.get_shape();
+        let data = L::all()
+            .into_iter()
+            .map(|layer| Array2::from_shape_fn(shape, |(y, x)| f(layer.clone(), Point2::new(x, y))))
+            .collect();
+
+        Self {
+            data,
+            metadata: params.into(),
+            params,
+            layer_type: PhantomData,
+        }
+    }
+
+    /// Creates a new [`CellMap`] from the given params, filling each cell by calling `f` with its
+    /// layer and the parent-frame position of the cell's centre.
+    ///
+    /// This is the positioned variant of [`CellMap::new_from_fn`], computing each cell's centre
+    /// via [`CellMapMetadata::position_unchecked`] before handing it to `f`.
+    ///
+    /// [`CellMapMetadata::position_unchecked`]: crate::map_metadata::CellMapMetadata::position_unchecked
+    pub fn new_from_fn_positioned<F>(params: CellMapParams, mut f: F) -> Self
+    where
+        F: FnMut(L, Point2<f64>) -> T,
+    {
+        let metadata: CellMapMetadata = params.into();
+        let shape = params.cell_bounds.get_shape();
+        let data = L::all()
+            .into_iter()
+            .map(|layer| {
+                Array2::from_shape_fn(shape, |(y, x)| {
+                    f(
+                        layer.clone(),
+                        metadata.position_unchecked(Point2::new(x, y)),
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            data,
+            metadata,
+            params,
+            layer_type: PhantomData,
+        }
+    }
+
     /// Returns the size of the cells in the map.
     pub fn cell_size(&self) -> Vector2<f64> {
         self.metadata.cell_size
@@ -178,6 +240,18 @@ where
         self.params
     }
 
+    /// Returns `true` if this map has storage for `layer`.
+    ///
+    /// For layers with a fixed, compile-time-known set (anything using `#[derive(Layer)]`) this
+    /// is always `true`, since every index in `0..L::NUM_LAYERS` always has storage. It's only
+    /// meaningful for [`DynamicLayer`](crate::DynamicLayer), where [`Layer::all`] can return
+    /// names interned by a different [`CellMap<DynamicLayer, _>`](CellMap) that this particular
+    /// map never added a layer for, or has since removed one for (freeing its storage).
+    pub(crate) fn has_layer(&self, layer: &L) -> bool {
+        let index = layer.to_index();
+        index < self.data.len() && !self.data[index].is_empty()
+    }
+
     /// Gets the [`nalgebra::Affine2<f64>`] transformation between the map frame and the parent
     /// frame.
     pub fn to_parent(&self) -> Affine2<f64> {
@@ -309,6 +383,24 @@ where
         self.metadata.index_unchecked(position)
     }
 
+    /// Returns an iterator over the cell indices crossed by the straight segment from
+    /// `start_parent` to `end_parent` (positions in the parent frame), stepped with the integer
+    /// Bresenham algorithm.
+    ///
+    /// See [`CellMapMetadata::line_cells`] for the full behaviour, including how `traversal`
+    /// affects diagonal steps and how the iterator clips to the map.
+    ///
+    /// [`CellMapMetadata::line_cells`]: crate::map_metadata::CellMapMetadata::line_cells
+    pub fn line_cells(
+        &self,
+        start_parent: Point2<f64>,
+        end_parent: Point2<f64>,
+        traversal: LineTraversal,
+    ) -> BresenhamLineIter {
+        self.metadata
+            .line_cells(start_parent, end_parent, traversal)
+    }
+
     /// Returns an iterator over each cell in all layers of the map.
     pub fn iter(&self) -> CellMapIter<'_, L, T, Many<L>, Cells> {
         CellMapIter::<'_, L, T, Many<L>, Cells>::new_cells(self)
@@ -319,11 +411,114 @@ where
         CellMapIterMut::<'_, L, T, Many<L>, Cells>::new_cells(self)
     }
 
+    /// Returns an iterator over each cell in all layers of the map whose center lies between
+    /// `corner_a` and `corner_b` (positions in the map's parent frame, in either order), clamped
+    /// to the map's own extent.
+    ///
+    /// This is the restricted-region counterpart to [`CellMap::iter`], letting you scan a local
+    /// patch (e.g. the area immediately around a robot) of a large map without walking every cell.
+    pub fn region_iter(
+        &self,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> CellMapIter<'_, L, T, Many<L>, Cells> {
+        CellMapIter::<'_, L, T, Many<L>, Cells>::new_cells_region(self, corner_a, corner_b)
+    }
+
+    /// Returns a mutable iterator over each cell in all layers of the map whose center lies
+    /// between `corner_a` and `corner_b` (positions in the map's parent frame, in either order),
+    /// clamped to the map's own extent.
+    ///
+    /// This is the restricted-region counterpart to [`CellMap::iter_mut`].
+    pub fn region_iter_mut(
+        &mut self,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> CellMapIterMut<'_, L, T, Many<L>, Cells> {
+        CellMapIterMut::<'_, L, T, Many<L>, Cells>::new_cells_region(self, corner_a, corner_b)
+    }
+
+    /// Returns an iterator over each cell in all layers of the map whose index lies inside the
+    /// rectangular sub-view described by `x` and `y`, clamped to the map's own extent.
+    ///
+    /// This is the cell-index counterpart to [`CellMap::region_iter`], for callers who already
+    /// have a bounding box in index space (e.g. the footprint of a sensor or a vehicle) rather
+    /// than a pair of parent-frame positions. `x`/`y` behave like `(Bound, Bound)` slice indexing
+    /// in the standard library, e.g. `map.slice_iter(2..5, ..)` scans columns 2 to 4 across every
+    /// row.
+    pub fn slice_iter(
+        &self,
+        x: impl std::ops::RangeBounds<usize>,
+        y: impl std::ops::RangeBounds<usize>,
+    ) -> CellMapIter<'_, L, T, Many<L>, SubGrid> {
+        CellMapIter::<'_, L, T, Many<L>, SubGrid>::new_sub_grid(self, x, y)
+    }
+
+    /// Returns a mutable iterator over each cell in all layers of the map whose index lies inside
+    /// the rectangular sub-view described by `x` and `y`, clamped to the map's own extent.
+    ///
+    /// This is the mutable counterpart to [`CellMap::slice_iter`].
+    pub fn slice_iter_mut(
+        &mut self,
+        x: impl std::ops::RangeBounds<usize>,
+        y: impl std::ops::RangeBounds<usize>,
+    ) -> CellMapIterMut<'_, L, T, Many<L>, SubGrid> {
+        CellMapIterMut::<'_, L, T, Many<L>, SubGrid>::new_sub_grid(self, x, y)
+    }
+
+    /// Returns an iterator over each cell in all layers of the map whose index lies inside the
+    /// intersection of `bounds` and the map's own [`cell_bounds`](Self::cell_bounds).
+    ///
+    /// This is the [`Bounds`]-based counterpart to [`CellMap::slice_iter`], for callers who
+    /// already have a region described in the map's own cell-bounds space (as returned by
+    /// [`CellMap::cell_bounds`], or built with [`Bounds::new`]) rather than a pair of `usize`
+    /// index ranges, analogous to [`BTreeMap::range`](std::collections::BTreeMap::range) yielding
+    /// only keys within a requested interval. A `bounds` that doesn't overlap the map at all
+    /// yields an iterator over zero cells.
+    pub fn iter_in(&self, bounds: Bounds) -> CellMapIter<'_, L, T, Many<L>, SubGrid> {
+        let (x, y) = self.clamp_bounds_to_index_ranges(bounds);
+        self.slice_iter(x, y)
+    }
+
+    /// Returns a mutable iterator over each cell in all layers of the map whose index lies inside
+    /// the intersection of `bounds` and the map's own [`cell_bounds`](Self::cell_bounds).
+    ///
+    /// This is the mutable counterpart to [`CellMap::iter_in`].
+    pub fn iter_in_mut(&mut self, bounds: Bounds) -> CellMapIterMut<'_, L, T, Many<L>, SubGrid> {
+        let (x, y) = self.clamp_bounds_to_index_ranges(bounds);
+        self.slice_iter_mut(x, y)
+    }
+
+    /// Intersects `bounds` with this map's own [`cell_bounds`](Self::cell_bounds) and converts the
+    /// result from cell-bounds space into the `0..num_cells` index ranges [`CellMap::slice_iter`]
+    /// expects.
+    fn clamp_bounds_to_index_ranges(
+        &self,
+        bounds: Bounds,
+    ) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let own = self.cell_bounds();
+
+        let x_lo = bounds.x.0.max(own.x.0);
+        let x_hi = bounds.x.1.min(own.x.1).max(x_lo);
+        let y_lo = bounds.y.0.max(own.y.0);
+        let y_hi = bounds.y.1.min(own.y.1).max(y_lo);
+
+        (
+            (x_lo - own.x.0) as usize..(x_hi - own.x.0) as usize,
+            (y_lo - own.y.0) as usize..(y_hi - own.y.0) as usize,
+        )
+    }
+
     /// Returns an iterator over windows of cells in the map.
     ///
     /// The `semi_width` is half the size of the window in the x and y axes, not including
     /// the central cell. E.g. to have a window which is in total 5x5, the `semi_window_size` needs
     /// to be `Vector2::new(2, 2)`.
+    ///
+    /// Returns [`Error::WindowedIterOnScrolledMap`] if the map has ever been scrolled by
+    /// [`CellMap::move_by`] or [`CellMap::move_to`], since a window that straddles the ring
+    /// buffer's wrap point can't be expressed as a single contiguous view. Use
+    /// [`CellMap::padded_window_iter`] instead, which handles this correctly.
     pub fn window_iter(
         &self,
         semi_width: Vector2<usize>,
@@ -336,6 +531,9 @@ where
     /// The `semi_width` is half the size of the window in the x and y axes, not including
     /// the central cell. E.g. to have a window which is in total 5x5, the `semi_window_size` needs
     /// to be `Vector2::new(2, 2)`.
+    ///
+    /// Returns [`Error::WindowedIterOnScrolledMap`] if the map has ever been scrolled by
+    /// [`CellMap::move_by`] or [`CellMap::move_to`]; see [`CellMap::window_iter`].
     pub fn window_iter_mut(
         &mut self,
         semi_width: Vector2<usize>,
@@ -343,6 +541,71 @@ where
         CellMapIterMut::<'_, L, T, Many<L>, Windows>::new_windows(self, semi_width)
     }
 
+    /// Returns an iterator over windows of cells in the map, for an arbitrary window `extent`
+    /// (full `(width, height)`) and `anchor` (the offset within the window, from its `(0, 0)`
+    /// corner, of the "current" cell the iterator's index reports).
+    ///
+    /// Unlike [`CellMap::window_iter`], which only produces centred, odd-sized windows, `extent`
+    /// and `anchor` can be any combination with `anchor` inside `extent`, e.g. a `(2, 1)` extent
+    /// with a `(0, 0)` anchor for a forward-difference gradient, or a `(3, 5)` extent for a
+    /// non-square tiling stencil.
+    ///
+    /// Returns [`Error::WindowedIterOnScrolledMap`] if the map has ever been scrolled; see
+    /// [`CellMap::window_iter`].
+    pub fn window_iter_asym(
+        &self,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, Windows>, Error> {
+        CellMapIter::<'_, L, T, Many<L>, Windows>::new_windows_asym(self, extent, anchor)
+    }
+
+    /// Returns a mutable iterator over windows of cells in the map, for an arbitrary window
+    /// `extent` and `anchor`. See [`CellMap::window_iter_asym`] for details.
+    pub fn window_iter_asym_mut(
+        &mut self,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, Windows>, Error> {
+        CellMapIterMut::<'_, L, T, Many<L>, Windows>::new_windows_asym(self, extent, anchor)
+    }
+
+    /// Returns an iterator over windows of cells in the map, restricted to the sub-rectangle
+    /// whose centers lie between `corner_a` and `corner_b` (positions in the map's parent frame,
+    /// in either order), clamped to the map's own extent.
+    ///
+    /// `semi_width` is as for [`CellMap::window_iter`]. This is the restricted-region counterpart
+    /// to it, letting you scan a local patch of a large map without walking every cell.
+    ///
+    /// Returns [`Error::WindowedIterOnScrolledMap`] if the map has ever been scrolled; see
+    /// [`CellMap::window_iter`].
+    pub fn window_region_iter(
+        &self,
+        semi_width: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, Windows>, Error> {
+        CellMapIter::<'_, L, T, Many<L>, Windows>::new_windows_region(
+            self, semi_width, corner_a, corner_b,
+        )
+    }
+
+    /// Returns a mutable iterator over windows of cells in the map, restricted to the
+    /// sub-rectangle whose centers lie between `corner_a` and `corner_b` (positions in the map's
+    /// parent frame, in either order), clamped to the map's own extent.
+    ///
+    /// This is the restricted-region counterpart to [`CellMap::window_iter_mut`].
+    pub fn window_region_iter_mut(
+        &mut self,
+        semi_width: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, Windows>, Error> {
+        CellMapIterMut::<'_, L, T, Many<L>, Windows>::new_windows_region(
+            self, semi_width, corner_a, corner_b,
+        )
+    }
+
     /// Returns an iterator over cells along the line joining `start_position` and
     /// `end_position`, which are expressed as positions in the map's parent frame.
     pub fn line_iter(
@@ -362,6 +625,346 @@ where
     ) -> Result<CellMapIterMut<'_, L, T, Many<L>, Line>, Error> {
         CellMapIterMut::<'_, L, T, Many<L>, Line>::new_line(self, start_position, end_position)
     }
+
+    /// Alias for [`CellMap::line_iter`], which already walks every cell the segment touches (a
+    /// "supercover" line) rather than the minimal Bresenham set, via the grid-crossing algorithm
+    /// described in [`Line`]'s own docs. Kept as a separate name for callers thinking in terms of
+    /// ray-casting occupancy updates, where "supercover" is the more familiar term.
+    ///
+    /// [`Line`]: crate::iterators::slicers::Line
+    pub fn supercover_iter(
+        &self,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, Line>, Error> {
+        self.line_iter(start_position, end_position)
+    }
+
+    /// Mutable counterpart to [`CellMap::supercover_iter`].
+    pub fn supercover_iter_mut(
+        &mut self,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, Line>, Error> {
+        self.line_iter_mut(start_position, end_position)
+    }
+
+    /// Returns an iterator over every cell within `half_width` cells of the line joining
+    /// `start_position` and `end_position`, which are expressed as positions in the map's parent
+    /// frame, the width-parameterized counterpart to [`CellMap::line_iter`].
+    pub fn thick_line_iter(
+        &self,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        half_width: f64,
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, ThickLine>, Error> {
+        CellMapIter::<'_, L, T, Many<L>, ThickLine>::new_thick_line(
+            self,
+            start_position,
+            end_position,
+            half_width,
+        )
+    }
+
+    /// Returns a mutable iterator over every cell within `half_width` cells of the line joining
+    /// `start_position` and `end_position`, which are expressed as positions in the map's parent
+    /// frame, the width-parameterized counterpart to [`CellMap::line_iter_mut`].
+    pub fn thick_line_iter_mut(
+        &mut self,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        half_width: f64,
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, ThickLine>, Error> {
+        CellMapIterMut::<'_, L, T, Many<L>, ThickLine>::new_thick_line(
+            self,
+            start_position,
+            end_position,
+            half_width,
+        )
+    }
+
+    /// Returns an iterator over every cell whose center lies inside the polygon described by
+    /// `vertices`, which are positions in the map's parent frame, in `(x, y)` raster order.
+    ///
+    /// Returns [`Error::PolygonTooFewVertices`] if fewer than 3 vertices are given.
+    pub fn polygon_iter(
+        &self,
+        vertices: &[Point2<f64>],
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, Polygon>, Error> {
+        CellMapIter::<'_, L, T, Many<L>, Polygon>::new_polygon(self, vertices)
+    }
+
+    /// Returns a mutable iterator over every cell whose center lies inside the polygon described
+    /// by `vertices`, which are positions in the map's parent frame, in `(x, y)` raster order.
+    ///
+    /// Returns [`Error::PolygonTooFewVertices`] if fewer than 3 vertices are given.
+    pub fn polygon_iter_mut(
+        &mut self,
+        vertices: &[Point2<f64>],
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, Polygon>, Error> {
+        CellMapIterMut::<'_, L, T, Many<L>, Polygon>::new_polygon(self, vertices)
+    }
+
+    /// Returns an iterator over every cell within `radius` of `center` (a position in the map's
+    /// parent frame) under the given [`DiskMetric`], in `(x, y)` raster order.
+    pub fn disk_iter(
+        &self,
+        center: Point2<f64>,
+        radius: f64,
+        metric: DiskMetric,
+    ) -> CellMapIter<'_, L, T, Many<L>, Disk> {
+        CellMapIter::<'_, L, T, Many<L>, Disk>::new_disk(self, center, radius, metric)
+    }
+
+    /// Returns a mutable iterator over every cell within `radius` of `center` (a position in the
+    /// map's parent frame) under the given [`DiskMetric`], in `(x, y)` raster order.
+    pub fn disk_iter_mut(
+        &mut self,
+        center: Point2<f64>,
+        radius: f64,
+        metric: DiskMetric,
+    ) -> CellMapIterMut<'_, L, T, Many<L>, Disk> {
+        CellMapIterMut::<'_, L, T, Many<L>, Disk>::new_disk(self, center, radius, metric)
+    }
+
+    /// Returns an iterator over every cell reachable from `seeds`, in order of increasing
+    /// accumulated cost under `connectivity`, stopping at any cell for which `step_cost` returns
+    /// `None` (blocked).
+    ///
+    /// `step_cost` is called once per candidate cell with that cell's index and should return the
+    /// cost of entering it, or `None` to treat it as impassable; pass `|_| Some(1.0)` for a plain
+    /// unweighted expansion. This is a Dijkstra / wavefront expansion, useful for building
+    /// distance fields or flood-filling from a set of start cells.
+    pub fn wavefront_iter<F>(
+        &self,
+        seeds: Vec<Point2<usize>>,
+        connectivity: WavefrontConnectivity,
+        step_cost: F,
+    ) -> CellMapIter<'_, L, T, Many<L>, Wavefront<F>>
+    where
+        F: Fn(Point2<usize>) -> Option<f64>,
+    {
+        CellMapIter::<'_, L, T, Many<L>, Wavefront<F>>::new_wavefront(
+            self,
+            seeds,
+            connectivity,
+            step_cost,
+        )
+    }
+
+    /// Returns a mutable iterator over every cell reachable from `seeds`, in order of increasing
+    /// accumulated cost under `connectivity`, stopping at any cell for which `step_cost` returns
+    /// `None` (blocked).
+    ///
+    /// See [`CellMap::wavefront_iter`] for details.
+    pub fn wavefront_iter_mut<F>(
+        &mut self,
+        seeds: Vec<Point2<usize>>,
+        connectivity: WavefrontConnectivity,
+        step_cost: F,
+    ) -> CellMapIterMut<'_, L, T, Many<L>, Wavefront<F>>
+    where
+        F: Fn(Point2<usize>) -> Option<f64>,
+    {
+        CellMapIterMut::<'_, L, T, Many<L>, Wavefront<F>>::new_wavefront(
+            self,
+            seeds,
+            connectivity,
+            step_cost,
+        )
+    }
+
+    /// Walks the cells of `layer` along the line joining `start_position` and `end_position`,
+    /// stopping as soon as `pred` returns `true` for a visited cell.
+    ///
+    /// Returns every cell visited up to and including the first one `pred` matched, paired with
+    /// its index, along with the index within that list of the matching cell (`None` if the line
+    /// was fully traversed without a match). This is intended for things like stopping a
+    /// simulated sensor ray as soon as it hits an occupied cell, without needing to first collect
+    /// the whole [`CellMap::line_iter`] and then search it.
+    pub fn line_iter_until<F>(
+        &self,
+        layer: L,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        mut pred: F,
+    ) -> Result<(Vec<(Point2<usize>, &T)>, Option<usize>), Error>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut visited = Vec::new();
+        let mut hit = None;
+
+        for ((_, idx), value) in self
+            .line_iter(start_position, end_position)?
+            .layer(layer)
+            .indexed()
+        {
+            let matched = pred(value);
+            visited.push((Point2::new(idx.x, idx.y), value));
+
+            if matched {
+                hit = Some(visited.len() - 1);
+                break;
+            }
+        }
+
+        Ok((visited, hit))
+    }
+
+    /// Returns a `rayon` parallel iterator over every cell in every layer of the map, in the same
+    /// layer-y-x order as [`CellMap::iter()`].
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> crate::iterators::par_iter::CellMapParIter<'_, L, T> {
+        crate::iterators::par_iter::CellMapParIter { map: self }
+    }
+
+    /// Returns a mutable `rayon` parallel iterator over every cell in every layer of the map, in
+    /// the same layer-y-x order as [`CellMap::iter_mut()`].
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> crate::iterators::par_iter::CellMapParIterMut<'_, L, T> {
+        crate::iterators::par_iter::CellMapParIterMut { map: self }
+    }
+
+    /// Returns a `rayon` parallel iterator over every cell in a single layer of the map, in the
+    /// same y-x order as [`CellMap::iter()`]`.layer(layer)`.
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    ///
+    /// [`CellMap::iter()`]: crate::CellMap::iter
+    #[cfg(feature = "rayon")]
+    pub fn par_layer_iter(
+        &self,
+        layer: L,
+    ) -> crate::iterators::par_iter::CellMapParLayerIter<'_, T> {
+        crate::iterators::par_iter::CellMapParLayerIter {
+            data: &self.data[layer.to_index()],
+        }
+    }
+
+    /// Returns a mutable `rayon` parallel iterator over every cell in a single layer of the map,
+    /// in the same y-x order as [`CellMap::iter_mut()`]`.layer(layer)`.
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    ///
+    /// [`CellMap::iter_mut()`]: crate::CellMap::iter_mut
+    #[cfg(feature = "rayon")]
+    pub fn par_layer_iter_mut(
+        &mut self,
+        layer: L,
+    ) -> crate::iterators::par_iter::CellMapParLayerIterMut<'_, T> {
+        crate::iterators::par_iter::CellMapParLayerIterMut {
+            data: &mut self.data[layer.to_index()],
+        }
+    }
+
+    /// Returns a `rayon` parallel iterator over windows of cells in the map, in the same
+    /// layer-y-x order as [`CellMap::window_iter()`].
+    ///
+    /// `semi_width` is as for [`CellMap::window_iter()`]. Unlike [`CellMap::par_iter_mut()`],
+    /// there's no mutable counterpart: neighbouring windows overlap, so handing them out to
+    /// different threads at the same time would let two threads write to the same cell.
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    ///
+    /// [`CellMap::window_iter()`]: crate::CellMap::window_iter
+    #[cfg(feature = "rayon")]
+    pub fn par_window_iter(
+        &self,
+        semi_width: Vector2<usize>,
+    ) -> Result<crate::iterators::par_iter::CellMapParWindowIter<'_, L, T>, Error> {
+        crate::iterators::par_iter::CellMapParWindowIter::new(self, semi_width)
+    }
+
+    /// Evaluates `f` over every centred `(2 * radius + 1)`-sized window of every layer, in
+    /// parallel, writing each result into the matching cell of a freshly built output map with the
+    /// same `cell_size`/`centre`/`num_cells` as `self`.
+    ///
+    /// `border` controls what happens to the parts of a window which fall outside the map; see
+    /// [`BorderMode`]. This is the parallel way to do convolution-style passes over a map --
+    /// smoothing a height layer, deriving a gradient or roughness layer, inflating an obstacle
+    /// layer -- which [`CellMap::par_iter_mut()`] can't do, since neighbouring windows overlap and
+    /// handing overlapping mutable windows to different threads would let them race on the same
+    /// cell. Reading from `self` and writing to a separate output map sidesteps that: every task's
+    /// read window may overlap another's, but every task's output cell is distinct.
+    ///
+    /// `f` must be a pure function of the window it's given -- it must not depend on anything
+    /// outside that window, such as shared mutable state -- which is what makes filling the output
+    /// map in parallel sound.
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    ///
+    /// [`BorderMode`]: crate::iterators::par_iter::BorderMode
+    #[cfg(feature = "rayon")]
+    pub fn par_window_map<F>(
+        &self,
+        radius: Vector2<usize>,
+        border: crate::iterators::par_iter::BorderMode<T>,
+        f: F,
+    ) -> Self
+    where
+        L: Sync,
+        T: Clone + Default + Sync,
+        F: Fn(&Array2<T>) -> T + Sync,
+    {
+        let mut out = Self::new_from_elem(self.params(), T::default());
+
+        for layer in L::all() {
+            crate::iterators::par_iter::par_window_map_layer(
+                &self.data[layer.to_index()],
+                &mut out.data[layer.to_index()],
+                self.metadata.start_index,
+                radius,
+                &border,
+                &f,
+            );
+        }
+
+        out
+    }
+}
+
+impl<L, T> CellMap<L, T>
+where
+    L: Layer,
+    T: Clone + Default,
+{
+    /// Returns an iterator over windows of cells in the map, producing a window for every cell
+    /// (including those within `semi_width` of the map edge), with the out-of-bounds parts of
+    /// edge windows synthesised according to `padding`.
+    ///
+    /// This is the always-a-window counterpart to [`CellMap::window_iter`], which excludes the
+    /// `semi_width` border entirely. Since out-of-bounds cells aren't contiguous with the rest of
+    /// the map's storage, each window is an owned [`Array2<T>`] rather than a borrowed
+    /// [`ArrayView2`](ndarray::ArrayView2).
+    pub fn padded_window_iter(
+        &self,
+        semi_width: Vector2<usize>,
+        padding: WindowPadding,
+    ) -> Result<CellMapIter<'_, L, T, Many<L>, PaddedWindows>, Error> {
+        CellMapIter::<'_, L, T, Many<L>, PaddedWindows>::new_padded_windows(
+            self, semi_width, padding,
+        )
+    }
+
+    /// Returns a mutable iterator over windows of cells in the map, producing a window for every
+    /// cell. See [`CellMap::padded_window_iter`] for details.
+    ///
+    /// Note that since each window is an owned copy, mutating it has no effect on the map; use
+    /// [`CellMap::window_iter_mut`] to mutate cells in place.
+    pub fn padded_window_iter_mut(
+        &mut self,
+        semi_width: Vector2<usize>,
+        padding: WindowPadding,
+    ) -> Result<CellMapIterMut<'_, L, T, Many<L>, PaddedWindows>, Error> {
+        CellMapIterMut::<'_, L, T, Many<L>, PaddedWindows>::new_padded_windows(
+            self, semi_width, padding,
+        )
+    }
 }
 
 impl<L, T> CellMap<L, T>
@@ -381,6 +984,24 @@ where
         let map_file = CellMapFile::new(&self);
         map_file.write_json(path)
     }
+
+    /// Writes the map to the given path using a compact binary `bincode` encoding.
+    ///
+    /// This is both faster and much more compact than [`CellMap::write_json`], at the cost of not
+    /// being human-readable, which matters for large maps of floating point data.
+    #[cfg(feature = "bincode")]
+    pub fn write_bincode<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        let map_file = CellMapFile::new(&self);
+        map_file.write_bincode(path)
+    }
+
+    /// Writes the map to the given path using `cell-map`'s mmap-compatible binary format, see
+    /// [`CellMapFile::write_mmap`].
+    #[cfg(feature = "mmap")]
+    pub fn write_mmap<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        let map_file = CellMapFile::new(&self);
+        map_file.write_mmap(path)
+    }
 }
 
 impl<L, T> CellMap<L, T>
@@ -394,6 +1015,22 @@ where
         let map_file = CellMapFile::from_json(path)?;
         map_file.into_cell_map()
     }
+
+    /// Loads a map stored in `bincode` format at the given path, as written by
+    /// [`CellMap::write_bincode`].
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let map_file = CellMapFile::from_bincode(path)?;
+        map_file.into_cell_map()
+    }
+
+    /// Loads a map written by [`CellMap::write_mmap`], memory-mapping the file rather than
+    /// reading it all upfront. See [`CellMapFile::from_mmap_file`] for details of the format.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let map_file = CellMapFile::from_mmap_file(path)?;
+        map_file.into_cell_map()
+    }
 }
 
 impl<L, T> CellMap<L, T>
@@ -412,6 +1049,148 @@ where
             layer_type: PhantomData,
         }
     }
+
+    /// Moves the map by `delta_cells`, recentering its logical view without reallocating or
+    /// copying the whole map.
+    ///
+    /// This is implemented as a per-axis circular buffer: moving the map only advances
+    /// [`CellMapMetadata::start_index`] and overwrites the rows/columns that have wrapped around
+    /// with `fill`, making this `O(number of newly-exposed cells)` rather than `O(whole map)`.
+    /// The leading edge (in the direction of `delta_cells`) becomes fresh cells containing
+    /// `fill`, while the trailing edge's old data is discarded.
+    ///
+    /// The map's position in the parent frame is updated by the equivalent sub-cell offset, so
+    /// that cells which didn't move keep the same parent-frame position.
+    ///
+    /// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+    pub fn move_by(&mut self, delta_cells: Vector2<isize>, fill: T) {
+        let num_cells = self.metadata.num_cells;
+
+        // Clamp the delta to the size of the map: moving further than the map is wide/tall
+        // overwrites every cell anyway.
+        let dx = delta_cells
+            .x
+            .clamp(-(num_cells.x as isize), num_cells.x as isize);
+        let dy = delta_cells
+            .y
+            .clamp(-(num_cells.y as isize), num_cells.y as isize);
+
+        // The logical rows/columns that are newly exposed by this move, and therefore need
+        // overwriting with `fill` rather than keeping their stale contents.
+        let new_cols: Vec<usize> = if dx >= 0 {
+            ((num_cells.x as isize - dx).max(0) as usize..num_cells.x).collect()
+        } else {
+            (0..(-dx) as usize).collect()
+        };
+        let new_rows: Vec<usize> = if dy >= 0 {
+            ((num_cells.y as isize - dy).max(0) as usize..num_cells.y).collect()
+        } else {
+            (0..(-dy) as usize).collect()
+        };
+
+        // Advance the ring buffer's origin. `rem_euclid` keeps the result in `0..num_cells` even
+        // for a negative delta.
+        self.metadata.start_index = Vector2::new(
+            (self.metadata.start_index.x as isize + dx).rem_euclid(num_cells.x as isize) as usize,
+            (self.metadata.start_index.y as isize + dy).rem_euclid(num_cells.y as isize) as usize,
+        );
+
+        // Overwrite the newly-exposed cells in every layer with `fill`.
+        for layer_data in self.data.iter_mut() {
+            for &y in &new_rows {
+                for x in 0..num_cells.x {
+                    let phys = self.metadata.wrap_index(Point2::new(x, y));
+                    layer_data[(phys.y, phys.x)] = fill.clone();
+                }
+            }
+            for &x in &new_cols {
+                for y in 0..num_cells.y {
+                    let phys = self.metadata.wrap_index(Point2::new(x, y));
+                    layer_data[(phys.y, phys.x)] = fill.clone();
+                }
+            }
+        }
+
+        // Shift the map's origin in the parent frame by the corresponding sub-cell offset, so
+        // that cells which didn't move keep the same parent-frame position.
+        // `to_parent` works in cell units (it already scales by `cell_size`).
+        let offset_in_map = Vector2::new(dx as f64, dy as f64);
+        let offset_in_parent = self.metadata.to_parent.transform_vector(&offset_in_map);
+        self.params.position_in_parent += offset_in_parent;
+        self.metadata.to_parent = CellMapMetadata::calc_to_parent(
+            self.params.position_in_parent,
+            self.params.rotation_in_parent_rad,
+            self.metadata.cell_size,
+        );
+    }
+
+    /// Moves the centre of the map as close as possible to `new_centre`, a position in the parent
+    /// frame, snapping to the nearest whole cell and calling [`CellMap::move_by`].
+    pub fn move_to(&mut self, new_centre: Point2<f64>, fill: T) {
+        let num_cells = self.metadata.num_cells;
+        let current_centre = self.metadata.to_parent.transform_point(&Point2::new(
+            num_cells.x as f64 / 2.0,
+            num_cells.y as f64 / 2.0,
+        ));
+
+        let offset_in_parent = new_centre - current_centre;
+        let offset_in_map = self
+            .metadata
+            .to_parent
+            .inverse_transform_vector(&offset_in_parent);
+
+        let delta_cells = Vector2::new(
+            offset_in_map.x.round() as isize,
+            offset_in_map.y.round() as isize,
+        );
+
+        self.move_by(delta_cells, fill);
+    }
+
+    /// Applies a log-odds style inverse sensor model update along the path of cells from
+    /// `start_position` to `end_position` on `layer`, as walked by the [`Line`] slicer (every cell
+    /// the segment crosses, see [`CellMap::supercover_iter`]).
+    ///
+    /// Every traversed cell is passed to `update_fn` along with a target value: `free_val` for
+    /// every cell the ray passes through, and `occ_val` for the final (endpoint) cell. `update_fn`
+    /// decides how to blend the cell's current value towards that target, e.g. a log-odds update
+    /// like `*cell = (*cell + target).clamp(min, max)`. This is the common "mark the ray free,
+    /// mark its endpoint occupied" update used to integrate range sensor (e.g. lidar) returns into
+    /// an occupancy layer, without every caller needing to re-implement the traversal themselves.
+    ///
+    /// [`Line`]: crate::iterators::slicers::Line
+    pub fn update_ray<F>(
+        &mut self,
+        layer: L,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        free_val: T,
+        occ_val: T,
+        mut update_fn: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&mut T, &T),
+    {
+        let end_index = self
+            .index(end_position)
+            .ok_or_else(|| Error::PositionOutsideMap("update_ray::End".into(), end_position))?;
+
+        for ((_, idx), value) in self
+            .line_iter_mut(start_position, end_position)?
+            .layer(layer)
+            .indexed()
+        {
+            let target = if idx.x == end_index.x && idx.y == end_index.y {
+                &occ_val
+            } else {
+                &free_val
+            };
+
+            update_fn(value, target);
+        }
+
+        Ok(())
+    }
 }
 
 impl<L, T> CellMap<L, T>
@@ -469,6 +1248,27 @@ where
         self.metadata.num_cells = new_bounds.get_num_cells();
     }
 
+    /// Returns a cropped (or expanded) copy of this map, bounded by the given pair of
+    /// [`Bound<isize>`](std::ops::Bound) ranges on the x and y axes.
+    ///
+    /// `Unbounded` resolves to this map's own extent on that edge, mirroring the `(Bound, Bound)`
+    /// slice-index support on Rust's core slices, e.g.
+    /// `map.view((Included(-3), Unbounded), (Excluded(0), Included(5)))`. Any part of the
+    /// requested view outside the current map is filled with `T::default()`, just as with
+    /// [`resize`](Self::resize).
+    pub fn view(
+        &self,
+        x: (Bound<isize>, Bound<isize>),
+        y: (Bound<isize>, Bound<isize>),
+    ) -> Result<Self, Error> {
+        let view_bounds = Bounds::from_bound_pairs(x, y, &self.metadata.cell_bounds)?;
+
+        let mut view = self.clone();
+        view.resize(view_bounds);
+
+        Ok(view)
+    }
+
     /// Merge `other` into self, resizing `self` so that `other` will be fully included in the map.
     ///
     /// Both maps should belong to the same parent frame, and `other.cell_size <= self.cell_size`.
@@ -587,7 +1387,483 @@ where
     }
 }
 
-impl<L, T> Index<L> for CellMap<L, T>
+/// Controls how [`CellMap::map_windows`] treats cells of a window that fall outside the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderPolicy {
+    /// Treat out-of-bounds cells as `T::default()`.
+    Default,
+
+    /// Treat out-of-bounds cells as having the value of the nearest in-bounds cell.
+    ClampToEdge,
+}
+
+impl<L, T> CellMap<L, T>
+where
+    L: Layer,
+    T: Clone + Default,
+{
+    /// Slides a `(2*semi_width.x+1) x (2*semi_width.y+1)` window across `from`, reducing each
+    /// window to a single value with `reduce`, and writes the result into the same cell index of
+    /// `to`.
+    ///
+    /// Cells of the window which fall outside the map are handled according to `border`.
+    ///
+    /// `from` and `to` may be the same layer; see [`CellMap::map_windows_in_place`] for a
+    /// convenience wrapper that makes this explicit.
+    pub fn map_windows<F>(
+        &mut self,
+        from: L,
+        to: L,
+        semi_width: Vector2<usize>,
+        border: BorderPolicy,
+        reduce: F,
+    ) where
+        F: Fn(&Array2<T>) -> T,
+    {
+        let num_cells = self.num_cells();
+        let mut result = Array2::from_elem(self.cell_bounds().get_shape(), T::default());
+        let window_shape = (semi_width.y * 2 + 1, semi_width.x * 2 + 1);
+
+        for y in 0..num_cells.y {
+            for x in 0..num_cells.x {
+                let mut window = Array2::from_elem(window_shape, T::default());
+
+                for wy in 0..window_shape.0 {
+                    for wx in 0..window_shape.1 {
+                        let sx = x as isize + wx as isize - semi_width.x as isize;
+                        let sy = y as isize + wy as isize - semi_width.y as isize;
+
+                        let value = if sx >= 0
+                            && sy >= 0
+                            && (sx as usize) < num_cells.x
+                            && (sy as usize) < num_cells.y
+                        {
+                            let phys = self
+                                .metadata
+                                .wrap_index(Point2::new(sx as usize, sy as usize));
+                            self[from.clone()][(phys.y, phys.x)].clone()
+                        } else {
+                            match border {
+                                BorderPolicy::Default => T::default(),
+                                BorderPolicy::ClampToEdge => {
+                                    let cx = sx.clamp(0, num_cells.x as isize - 1) as usize;
+                                    let cy = sy.clamp(0, num_cells.y as isize - 1) as usize;
+                                    let phys = self.metadata.wrap_index(Point2::new(cx, cy));
+                                    self[from.clone()][(phys.y, phys.x)].clone()
+                                }
+                            }
+                        };
+
+                        window[(wy, wx)] = value;
+                    }
+                }
+
+                let phys = self.metadata.wrap_index(Point2::new(x, y));
+                result[(phys.y, phys.x)] = reduce(&window);
+            }
+        }
+
+        self.data[to.to_index()] = result;
+    }
+
+    /// Convenience wrapper around [`CellMap::map_windows`] which reduces `layer` into itself.
+    pub fn map_windows_in_place<F>(
+        &mut self,
+        layer: L,
+        semi_width: Vector2<usize>,
+        border: BorderPolicy,
+        reduce: F,
+    ) where
+        F: Fn(&Array2<T>) -> T,
+    {
+        self.map_windows(layer.clone(), layer, semi_width, border, reduce)
+    }
+}
+
+/// Controls how [`CellMap::window_map`] and [`CellMap::window_map_into`] handle the `semi_width`
+/// border that [`Windows`] excludes, since a window can't be centred there without going out of
+/// bounds.
+#[derive(Debug, Clone)]
+pub enum WindowMapConfig<U> {
+    /// The output map is smaller than the input by `semi_width` on every edge, containing only
+    /// cells whose window was fully in bounds.
+    Shrink,
+
+    /// The output map is the same size as the input, with border cells set to this value.
+    Pad(U),
+
+    /// The output map is the same size as the input. Border windows are synthesised by clamping
+    /// the out-of-bounds part of the window to the nearest in-bounds cell, rather than being
+    /// filled with a caller-supplied constant.
+    Clamp,
+}
+
+impl<L, T> CellMap<L, T>
+where
+    L: Layer,
+{
+    /// Applies `f` to every window produced by [`CellMap::window_iter`] and collects the results
+    /// into a new map of the same layer set.
+    ///
+    /// This is the stencil/convolution counterpart to [`CellMap::map_windows`]: `map_windows`
+    /// reduces a window back into the same cell type `T` on one layer, whereas `window_map`
+    /// produces a whole new map and allows the output cell type `U` to differ from `T`. Since
+    /// [`Windows`] excludes the `semi_width` border around the map edge, `config` chooses whether
+    /// the output map is cropped to just the interior ([`WindowMapConfig::Shrink`]), left the same
+    /// size with the border filled from a supplied value ([`WindowMapConfig::Pad`]), or left the
+    /// same size with the border windows synthesised by clamping to the nearest in-bounds cell
+    /// ([`WindowMapConfig::Clamp`]). This makes common robotics map operations (mean/max
+    /// filtering, gradient/Sobel kernels, distance smoothing) a few lines instead of hand-rolled
+    /// index bookkeeping.
+    ///
+    /// [`Windows`]: crate::iterators::slicers::Windows
+    pub fn window_map<U, F>(
+        &self,
+        semi_width: Vector2<usize>,
+        config: WindowMapConfig<U>,
+        mut f: F,
+    ) -> Result<CellMap<L, U>, Error>
+    where
+        U: Clone,
+        T: Clone,
+        F: FnMut(ArrayView2<T>) -> U,
+    {
+        match config {
+            WindowMapConfig::Shrink => {
+                let num_cells = self.num_cells();
+
+                if num_cells.x <= 2 * semi_width.x || num_cells.y <= 2 * semi_width.y {
+                    return Err(Error::WindowLargerThanMap(
+                        semi_width * 2 + Vector2::new(1, 1),
+                        num_cells,
+                    ));
+                }
+
+                let cell_bounds = Bounds::new(
+                    (0, (num_cells.x - 2 * semi_width.x) as isize),
+                    (0, (num_cells.y - 2 * semi_width.y) as isize),
+                )?;
+                let shape = cell_bounds.get_shape();
+
+                // The interior's (0, 0) cell sits where the input map's (semi_width.x,
+                // semi_width.y) cell does, so translate the output map's origin to that corner in
+                // the parent frame.
+                let corner = self
+                    .metadata
+                    .to_parent
+                    .transform_point(&Point2::new(semi_width.x as f64, semi_width.y as f64));
+
+                let params = CellMapParams {
+                    cell_bounds,
+                    position_in_parent: Vector2::new(corner.x, corner.y),
+                    ..self.params
+                };
+
+                let mut data = Vec::with_capacity(L::NUM_LAYERS);
+                for layer in L::all() {
+                    let values = self
+                        .window_iter(semi_width)?
+                        .layer(layer)
+                        .map(&mut f)
+                        .collect::<Vec<_>>();
+
+                    data.push(Array2::from_shape_vec(shape, values).expect(
+                        "window_iter visits every interior cell exactly once in raster order",
+                    ));
+                }
+
+                Ok(CellMap {
+                    data,
+                    metadata: params.into(),
+                    params,
+                    layer_type: PhantomData,
+                })
+            }
+            WindowMapConfig::Pad(default) => {
+                let shape = self.cell_bounds().get_shape();
+                let mut data: Vec<Array2<U>> = L::all()
+                    .into_iter()
+                    .map(|_| Array2::from_elem(shape, default.clone()))
+                    .collect();
+
+                for layer in L::all() {
+                    for (index, window) in self
+                        .window_iter(semi_width)?
+                        .layer(layer.clone())
+                        .indexed()
+                        .map(|((_, index), window)| (index, window))
+                    {
+                        data[layer.to_index()][index.as_array2_index()] = f(window);
+                    }
+                }
+
+                Ok(CellMap {
+                    data,
+                    metadata: self.params.into(),
+                    params: self.params,
+                    layer_type: PhantomData,
+                })
+            }
+            WindowMapConfig::Clamp => {
+                let num_cells = self.num_cells();
+                let window_shape = (semi_width.y * 2 + 1, semi_width.x * 2 + 1);
+
+                if window_shape.0 > num_cells.y || window_shape.1 > num_cells.x {
+                    return Err(Error::WindowLargerThanMap(
+                        semi_width * 2 + Vector2::new(1, 1),
+                        num_cells,
+                    ));
+                }
+
+                let mut data = Vec::with_capacity(L::NUM_LAYERS);
+                for layer in L::all() {
+                    let layer_data = &self.data[layer.to_index()];
+
+                    // Every window is gathered cell-by-cell through `wrap_index` rather than taken
+                    // as a contiguous slice of `layer_data`, since a logically in-bounds window can
+                    // still straddle the ring buffer's wrap point in physical storage once the map
+                    // has been scrolled.
+                    let values = Array2::from_shape_fn((num_cells.y, num_cells.x), |(y, x)| {
+                        let sx = x as isize - semi_width.x as isize;
+                        let sy = y as isize - semi_width.y as isize;
+
+                        let owned = Array2::from_shape_fn(window_shape, |(wy, wx)| {
+                            let lx = sx + wx as isize;
+                            let ly = sy + wy as isize;
+
+                            let (cx, cy) = if lx >= 0
+                                && ly >= 0
+                                && (lx as usize) < num_cells.x
+                                && (ly as usize) < num_cells.y
+                            {
+                                (lx as usize, ly as usize)
+                            } else {
+                                (
+                                    lx.clamp(0, num_cells.x as isize - 1) as usize,
+                                    ly.clamp(0, num_cells.y as isize - 1) as usize,
+                                )
+                            };
+
+                            let phys = self.metadata.wrap_index(Point2::new(cx, cy));
+                            layer_data[[phys.y, phys.x]].clone()
+                        });
+
+                        f(owned.view())
+                    });
+
+                    data.push(values);
+                }
+
+                Ok(CellMap {
+                    data,
+                    metadata: self.params.into(),
+                    params: self.params,
+                    layer_type: PhantomData,
+                })
+            }
+        }
+    }
+
+    /// Applies `f` to every window produced by [`CellMap::window_iter`] and writes the results
+    /// into the matching cells of `dest`, which must have the same shape as `self`.
+    ///
+    /// Unlike [`CellMap::window_map`], this doesn't allocate a new map: cells within `semi_width`
+    /// of the edge (which [`Windows`] excludes) are left untouched in `dest`, so callers that
+    /// reuse the same destination map across updates (e.g. a costmap recomputed every tick) keep
+    /// whatever border values they already set up.
+    ///
+    /// [`Windows`]: crate::iterators::slicers::Windows
+    pub fn window_map_into<U, F>(
+        &self,
+        semi_width: Vector2<usize>,
+        dest: &mut CellMap<L, U>,
+        mut f: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(ArrayView2<T>) -> U,
+    {
+        if dest.num_cells() != self.num_cells() {
+            return Err(Error::LayerWrongShape(dest.num_cells(), self.num_cells()));
+        }
+
+        for layer in L::all() {
+            for (index, window) in self
+                .window_iter(semi_width)?
+                .layer(layer.clone())
+                .indexed()
+                .map(|((_, index), window)| (index, window))
+            {
+                dest[layer.clone()][index.as_array2_index()] = f(window);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<L, T> CellMap<L, T>
+where
+    L: Layer,
+    T: Clone + Default + Into<f64> + From<f64>,
+{
+    /// Inflates obstacle costs on `layer` in place, for use cases like costmap inflation around
+    /// obstacles.
+    ///
+    /// Each cell's cost becomes the maximum, over all neighbouring cells within `radius`, of
+    /// `neighbour_cost * decay.powf(distance)`, clamped so it never drops below the cell's
+    /// existing value. Cells outside the map are treated as the nearest in-bounds cell (see
+    /// [`BorderPolicy::ClampToEdge`]).
+    pub fn inflate_obstacles(&mut self, layer: L, radius: usize, decay: f64) {
+        self.map_windows_in_place(
+            layer,
+            Vector2::new(radius, radius),
+            BorderPolicy::ClampToEdge,
+            move |window| {
+                let centre = window[(radius, radius)].clone().into();
+                let mut best = centre;
+
+                for ((wy, wx), value) in window.indexed_iter() {
+                    let dy = wy as isize - radius as isize;
+                    let dx = wx as isize - radius as isize;
+                    let dist = ((dx * dx + dy * dy) as f64).sqrt();
+
+                    let scaled: f64 = value.clone().into() * decay.powf(dist);
+                    if scaled > best {
+                        best = scaled;
+                    }
+                }
+
+                T::from(best)
+            },
+        );
+    }
+
+    /// Inflates obstacle costs from a boolean `occupied` layer into `cost`, using a two-pass
+    /// chamfer distance transform rather than [`CellMap::inflate_obstacles`]'s per-cell window
+    /// scan.
+    ///
+    /// A cell is considered occupied if its value in `occupied` is non-zero. Distance to the
+    /// nearest occupied cell is approximated with integer chamfer weights (3 for orthogonal steps,
+    /// 4 for diagonal), relaxed in a forward pass (top-left to bottom-right) followed by a backward
+    /// pass (bottom-right to top-left) with the mirrored neighbour set, then divided by 3 to
+    /// approximate Euclidean distance. For every cell within `radius` of an obstacle, `cost` is set
+    /// to `decay(distance)`; farther cells are left unchanged in `cost`.
+    ///
+    /// The transform is restricted to the bounding box of occupied cells padded by `radius`
+    /// (clipped to the map), so inflation stays cheap on sparse maps. If `occupied` has no
+    /// occupied cells, `cost` is left untouched.
+    pub fn inflate_obstacles_chamfer<F>(&mut self, occupied: L, cost: L, radius: usize, decay: F)
+    where
+        F: Fn(f64) -> f64,
+    {
+        // A sentinel "far" distance that two chamfer steps (8) can't overflow even after many
+        // relaxations across a large map.
+        const FAR: i64 = i64::MAX / 4;
+
+        let (rows, cols) = self[occupied.clone()].dim();
+
+        // Find the bounding box of occupied cells in logical index coordinates, going through
+        // the `(L, Point2<usize>)` index so cells are looked up at their ring-buffer-wrapped
+        // physical position rather than assuming logical and physical coordinates coincide.
+        let mut min_y = rows;
+        let mut max_y = 0;
+        let mut min_x = cols;
+        let mut max_x = 0;
+        let mut any_occupied = false;
+
+        for y in 0..rows {
+            for x in 0..cols {
+                let value: f64 = self[(occupied.clone(), Point2::new(x, y))].clone().into();
+                if value != 0.0 {
+                    any_occupied = true;
+                    min_y = min_y.min(y);
+                    max_y = max_y.max(y);
+                    min_x = min_x.min(x);
+                    max_x = max_x.max(x);
+                }
+            }
+        }
+
+        if !any_occupied {
+            return;
+        }
+
+        // Pad the bounding box by the inflation radius, clipped to the map's own extent.
+        let y0 = min_y.saturating_sub(radius);
+        let y1 = (max_y + radius + 1).min(rows);
+        let x0 = min_x.saturating_sub(radius);
+        let x1 = (max_x + radius + 1).min(cols);
+
+        let h = y1 - y0;
+        let w = x1 - x0;
+
+        let mut dist = Array2::from_elem((h, w), FAR);
+        for y in 0..h {
+            for x in 0..w {
+                let occupied_value: f64 = self[(occupied.clone(), Point2::new(x0 + x, y0 + y))]
+                    .clone()
+                    .into();
+                if occupied_value != 0.0 {
+                    dist[(y, x)] = 0;
+                }
+            }
+        }
+
+        // Forward pass: top-left -> bottom-right, relaxing against the neighbours already visited
+        // in this pass.
+        for y in 0..h {
+            for x in 0..w {
+                let mut best = dist[(y, x)];
+                if y > 0 {
+                    best = best.min(dist[(y - 1, x)] + 3);
+                    if x > 0 {
+                        best = best.min(dist[(y - 1, x - 1)] + 4);
+                    }
+                    if x + 1 < w {
+                        best = best.min(dist[(y - 1, x + 1)] + 4);
+                    }
+                }
+                if x > 0 {
+                    best = best.min(dist[(y, x - 1)] + 3);
+                }
+                dist[(y, x)] = best;
+            }
+        }
+
+        // Backward pass: bottom-right -> top-left, relaxing against the mirrored neighbour set.
+        for y in (0..h).rev() {
+            for x in (0..w).rev() {
+                let mut best = dist[(y, x)];
+                if y + 1 < h {
+                    best = best.min(dist[(y + 1, x)] + 3);
+                    if x + 1 < w {
+                        best = best.min(dist[(y + 1, x + 1)] + 4);
+                    }
+                    if x > 0 {
+                        best = best.min(dist[(y + 1, x - 1)] + 4);
+                    }
+                }
+                if x + 1 < w {
+                    best = best.min(dist[(y, x + 1)] + 3);
+                }
+                dist[(y, x)] = best;
+            }
+        }
+
+        // Map distances within radius through the decay function into cost, leaving farther cells
+        // untouched.
+        for y in 0..h {
+            for x in 0..w {
+                let approx_dist = dist[(y, x)] as f64 / 3.0;
+                if approx_dist <= radius as f64 {
+                    self[(cost.clone(), Point2::new(x0 + x, y0 + y))] = T::from(decay(approx_dist));
+                }
+            }
+        }
+    }
+}
+
+impl<L, T> Index<L> for CellMap<L, T>
 where
     L: Layer,
 {
@@ -614,7 +1890,8 @@ where
     type Output = T;
 
     fn index(&self, index: (L, Point2<usize>)) -> &Self::Output {
-        &self[index.0][(index.1.y, index.1.x)]
+        let phys = self.metadata.wrap_index(index.1);
+        &self[index.0][(phys.y, phys.x)]
     }
 }
 
@@ -623,7 +1900,8 @@ where
     L: Layer,
 {
     fn index_mut(&mut self, index: (L, Point2<usize>)) -> &mut Self::Output {
-        &mut self[index.0][(index.1.y, index.1.x)]
+        let phys = self.metadata.wrap_index(index.1);
+        &mut self[index.0][(phys.y, phys.x)]
     }
 }
 
@@ -725,6 +2003,41 @@ impl Bounds {
         )
     }
 
+    /// Creates a new bound from a pair of [`Bound<isize>`](std::ops::Bound) per axis, mirroring
+    /// the `(Bound, Bound)` slice-index support in the standard library.
+    ///
+    /// `Unbounded` resolves to the corresponding edge of `outer`. Otherwise each bound is turned
+    /// into this type's half-open `(min, max)` convention: an `Included` start maps to `n`, an
+    /// `Excluded` start to `n + 1`, an `Included` end to `n + 1`, and an `Excluded` end to `n`.
+    /// Returns [`Error::BoundsOverflow`] if resolving a bound overflows `isize`, or
+    /// [`Error::InvalidBounds`] if the resulting range is inverted.
+    pub fn from_bound_pairs(
+        x: (Bound<isize>, Bound<isize>),
+        y: (Bound<isize>, Bound<isize>),
+        outer: &Bounds,
+    ) -> Result<Self, Error> {
+        fn resolve_start(bound: Bound<isize>, default: isize) -> Result<isize, Error> {
+            match bound {
+                Bound::Included(n) => Ok(n),
+                Bound::Excluded(n) => n.checked_add(1).ok_or(Error::BoundsOverflow),
+                Bound::Unbounded => Ok(default),
+            }
+        }
+
+        fn resolve_end(bound: Bound<isize>, default: isize) -> Result<isize, Error> {
+            match bound {
+                Bound::Included(n) => n.checked_add(1).ok_or(Error::BoundsOverflow),
+                Bound::Excluded(n) => Ok(n),
+                Bound::Unbounded => Ok(default),
+            }
+        }
+
+        let x_bounds = (resolve_start(x.0, outer.x.0)?, resolve_end(x.1, outer.x.1)?);
+        let y_bounds = (resolve_start(y.0, outer.y.0)?, resolve_end(y.1, outer.y.1)?);
+
+        Self::new(x_bounds, y_bounds)
+    }
+
     /// Checks if the given point is inside the bounds
     pub fn contains(&self, point: Point2<isize>) -> bool {
         self.x.0 <= point.x && point.x < self.x.1 && self.y.0 <= point.y && point.y < self.y.1
@@ -792,6 +2105,50 @@ impl Bounds {
         .unwrap_or_default()
     }
 
+    /// Gets the region of `self` not covered by `other`, decomposed into at most four disjoint,
+    /// axis-aligned sub-bounds.
+    ///
+    /// This is a guillotine split relative to the intersection of `self` and `other`: a bottom and
+    /// top strip spanning the full width of `self`, and a left and right strip clamped to the
+    /// intersection's y range. Strips with zero width or height are omitted, so the result only
+    /// ever contains valid, non-empty bounds.
+    ///
+    /// If `self` and `other` don't intersect, the result is `vec![*self]`.
+    ///
+    /// This is useful for incremental map maintenance: when a robot's observed footprint moves,
+    /// the newly-revealed area is `new_bounds.difference(&old_bounds)`, and only those cells need
+    /// reinitialising.
+    pub fn difference(&self, other: &Bounds) -> Vec<Bounds> {
+        let intersect = match self.intersect(other) {
+            Some(i) => i,
+            None => return vec![*self],
+        };
+
+        let mut slabs = Vec::with_capacity(4);
+
+        let mut push_if_non_empty = |x: (isize, isize), y: (isize, isize)| {
+            if x.0 < x.1 && y.0 < y.1 {
+                if let Ok(bounds) = Bounds::new(x, y) {
+                    slabs.push(bounds);
+                }
+            }
+        };
+
+        // Bottom strip, below the intersection, spanning the full width of self
+        push_if_non_empty((self.x.0, self.x.1), (self.y.0, intersect.y.0));
+
+        // Top strip, above the intersection, spanning the full width of self
+        push_if_non_empty((self.x.0, self.x.1), (intersect.y.1, self.y.1));
+
+        // Left strip, clamped to the intersection's y range
+        push_if_non_empty((self.x.0, intersect.x.0), (intersect.y.0, intersect.y.1));
+
+        // Right strip, clamped to the intersection's y range
+        push_if_non_empty((intersect.x.1, self.x.1), (intersect.y.0, intersect.y.1));
+
+        slabs
+    }
+
     /// Gets the slice of other within self, cropping other so it fits within self.
     ///
     /// Note that slices are a pair of (min, max) half-open bounds that describe the slice into an
@@ -813,6 +2170,198 @@ impl Bounds {
             ),
         ))
     }
+
+    /// Splits this bounds into its four quadrant children: bottom-left, bottom-right, top-left,
+    /// and top-right, in that order.
+    ///
+    /// If an axis has an odd number of cells, the extra cell goes to the lower/left child on that
+    /// axis. The union of the four returned bounds is always exactly `self`, and each is fully
+    /// contained within it, which keeps [`Bounds::get_index`]/[`Bounds::get_slice_of_other`]
+    /// consistent no matter which resolution level they're used at.
+    pub fn subdivide(&self) -> [Bounds; 4] {
+        let mid_x = self.x.0 + (self.x.1 - self.x.0 + 1) / 2;
+        let mid_y = self.y.0 + (self.y.1 - self.y.0 + 1) / 2;
+
+        [
+            Bounds {
+                x: (self.x.0, mid_x),
+                y: (self.y.0, mid_y),
+            },
+            Bounds {
+                x: (mid_x, self.x.1),
+                y: (self.y.0, mid_y),
+            },
+            Bounds {
+                x: (self.x.0, mid_x),
+                y: (mid_y, self.y.1),
+            },
+            Bounds {
+                x: (mid_x, self.x.1),
+                y: (mid_y, self.y.1),
+            },
+        ]
+    }
+
+    /// Returns a coarser version of this bounds, halving its extent on each axis (rounding up),
+    /// keeping the same origin.
+    ///
+    /// Note that this always equals `self.subdivide()[0]`, since halving the extent from the same
+    /// origin is exactly how the bottom-left child is built.
+    pub fn coarsen(&self) -> Bounds {
+        self.subdivide()[0]
+    }
+
+    /// Intersects a ray with this bounds using the slab method, returning the clipped
+    /// `(entry, exit)` ray parameters if the ray hits, or `None` otherwise.
+    ///
+    /// `origin` and `dir` are in the same coordinate frame as this bounds. For each axis this
+    /// computes `t1 = (min - origin) / dir` and `t2 = (max - origin) / dir`, taking
+    /// `tmin = max(min(t1, t2))` and `tmax = min(max(t1, t2))` across both axes. The ray hits iff
+    /// `tmax >= tmin.max(0.0)`, in which case `(tmin.max(0.0), tmax)` is returned so the entry
+    /// parameter is never behind the ray's origin. An axis with `dir == 0.0` is treated as
+    /// parallel to that slab: the ray misses unless `origin` already lies within it on that axis.
+    pub fn ray_intersection(&self, origin: Point2<f64>, dir: Vector2<f64>) -> Option<(f64, f64)> {
+        fn slab(min: isize, max: isize, origin: f64, dir: f64) -> Option<(f64, f64)> {
+            if dir == 0.0 {
+                if origin < min as f64 || origin > max as f64 {
+                    None
+                } else {
+                    Some((f64::NEG_INFINITY, f64::INFINITY))
+                }
+            } else {
+                let t1 = (min as f64 - origin) / dir;
+                let t2 = (max as f64 - origin) / dir;
+                Some((t1.min(t2), t1.max(t2)))
+            }
+        }
+
+        let (tx_min, tx_max) = slab(self.x.0, self.x.1, origin.x, dir.x)?;
+        let (ty_min, ty_max) = slab(self.y.0, self.y.1, origin.y, dir.y)?;
+
+        let tmin = tx_min.max(ty_min);
+        let tmax = tx_max.min(ty_max);
+
+        if tmax >= tmin.max(0.0) {
+            Some((tmin.max(0.0), tmax))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every cell this bounds contains that the ray from `origin` in
+    /// direction `dir` passes through, using the Amanatides-Woo DDA algorithm.
+    ///
+    /// Returns `None` if [`Self::ray_intersection`] finds no hit. Otherwise the iterator starts at
+    /// the entry cell and steps one cell at a time along whichever axis has the smaller remaining
+    /// distance to its next cell boundary, until the ray's exit parameter is exceeded. This is the
+    /// core primitive for clearing free space along a sensor beam.
+    pub fn cells_along_ray(&self, origin: Point2<f64>, dir: Vector2<f64>) -> Option<RayCellIter> {
+        let (t_min, t_max) = self.ray_intersection(origin, dir)?;
+
+        let entry = origin + dir * t_min;
+        let current = Point2::new(entry.x.floor() as isize, entry.y.floor() as isize);
+
+        let step = Vector2::new(
+            if dir.x > 0.0 {
+                1
+            } else if dir.x < 0.0 {
+                -1
+            } else {
+                0
+            },
+            if dir.y > 0.0 {
+                1
+            } else if dir.y < 0.0 {
+                -1
+            } else {
+                0
+            },
+        );
+
+        let t_delta = Vector2::new(
+            if dir.x == 0.0 {
+                f64::INFINITY
+            } else {
+                (1.0 / dir.x).abs()
+            },
+            if dir.y == 0.0 {
+                f64::INFINITY
+            } else {
+                (1.0 / dir.y).abs()
+            },
+        );
+
+        let next_boundary = |cell: isize, step: isize| -> f64 {
+            if step > 0 {
+                (cell + 1) as f64
+            } else {
+                cell as f64
+            }
+        };
+
+        let t_max_axes = Vector2::new(
+            if step.x == 0 {
+                f64::INFINITY
+            } else {
+                (next_boundary(current.x, step.x) - origin.x) / dir.x
+            },
+            if step.y == 0 {
+                f64::INFINITY
+            } else {
+                (next_boundary(current.y, step.y) - origin.y) / dir.y
+            },
+        );
+
+        Some(RayCellIter {
+            bounds: *self,
+            current,
+            step,
+            t_delta,
+            t_max: t_max_axes,
+            t: t_min,
+            exit_t: t_max,
+        })
+    }
+}
+
+/// Iterator over the cells a ray passes through inside a [`Bounds`], produced by
+/// [`Bounds::cells_along_ray`].
+///
+/// Implements the Amanatides-Woo DDA algorithm: each step advances whichever axis has the smaller
+/// `t_max`, the ray parameter at which it next crosses a cell boundary.
+#[derive(Debug, Clone, Copy)]
+pub struct RayCellIter {
+    bounds: Bounds,
+    current: Point2<isize>,
+    step: Vector2<isize>,
+    t_delta: Vector2<f64>,
+    t_max: Vector2<f64>,
+    t: f64,
+    exit_t: f64,
+}
+
+impl Iterator for RayCellIter {
+    type Item = Point2<isize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.t > self.exit_t || !self.bounds.contains(self.current) {
+            return None;
+        }
+
+        let cell = self.current;
+
+        if self.t_max.x < self.t_max.y {
+            self.current.x += self.step.x;
+            self.t = self.t_max.x;
+            self.t_max.x += self.t_delta.x;
+        } else {
+            self.current.y += self.step.y;
+            self.t = self.t_max.y;
+            self.t_max.y += self.t_delta.y;
+        }
+
+        Some(cell)
+    }
 }
 
 impl Default for Bounds {
@@ -820,3 +2369,174 @@ impl Default for Bounds {
         Self::empty()
     }
 }
+
+/// Axis-aligned box describing the number of cells in each direction of a volumetric map.
+///
+/// This is the 3D sibling of [`Bounds`], for maps with a `z` axis such as stacked height layers or
+/// aerial occupancy grids. Like [`Bounds`], these bounds are a half-open range, i.e. satisfied in
+/// the ranges:
+///  - $x_0 <= x < x_1$
+///  - $y_0 <= y < y_1$
+///  - $z_0 <= z < z_1$
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Bounds3 {
+    /// The bounds on the x axis, in the format (min, max),
+    pub x: (isize, isize),
+
+    /// The bounds on the y axis, in the format (min, max),
+    pub y: (isize, isize),
+
+    /// The bounds on the z axis, in the format (min, max),
+    pub z: (isize, isize),
+}
+
+impl Bounds3 {
+    /// Creates a new empty (zero sized) bound
+    pub fn empty() -> Self {
+        Self {
+            x: (0, 0),
+            y: (0, 0),
+            z: (0, 0),
+        }
+    }
+
+    /// Returns if the bounds are valid or not, i.e. if the minimum is larger than the maximum on
+    /// any axis.
+    pub fn is_valid(&self) -> bool {
+        self.x.0 <= self.x.1 && self.y.0 <= self.y.1 && self.z.0 <= self.z.1
+    }
+
+    /// Creates a new bound from the given max and min cell indices in the x, y, and z axes.
+    ///
+    /// Must satisfy:
+    ///  - $x_0 <= x_1$
+    ///  - $y_0 <= y_1$
+    ///  - $z_0 <= z_1$
+    pub fn new(x: (isize, isize), y: (isize, isize), z: (isize, isize)) -> Result<Self, Error> {
+        let bounds = Self { x, y, z };
+
+        if bounds.is_valid() {
+            Ok(bounds)
+        } else {
+            Err(Error::InvalidBounds3(bounds))
+        }
+    }
+
+    /// Creates a new bound from the given opposing corners of the box.
+    ///
+    /// If the corners do not satisfy `all(bottom_left_near <= upper_right_far)` the bounds will be
+    /// invalid and an error is returned.
+    pub fn from_corners(
+        bottom_left_near: Point3<isize>,
+        upper_right_far: Point3<isize>,
+    ) -> Result<Self, Error> {
+        let bounds = Self {
+            x: (bottom_left_near.x, upper_right_far.x),
+            y: (bottom_left_near.y, upper_right_far.y),
+            z: (bottom_left_near.z, upper_right_far.z),
+        };
+
+        if bounds.is_valid() {
+            Ok(bounds)
+        } else {
+            Err(Error::InvalidBounds3(bounds))
+        }
+    }
+
+    /// Checks if the given point is inside the bounds
+    pub fn contains(&self, point: Point3<isize>) -> bool {
+        self.x.0 <= point.x
+            && point.x < self.x.1
+            && self.y.0 <= point.y
+            && point.y < self.y.1
+            && self.z.0 <= point.z
+            && point.z < self.z.1
+    }
+
+    /// Gets the value of the point as an index into an array bounded by this `Bounds3`.
+    ///
+    /// If the point is outside the bounds `None` is returned
+    pub fn get_index(&self, point: Point3<isize>) -> Option<Point3<usize>> {
+        if self.contains(point) {
+            Some(Point3::new(
+                (point.x - self.x.0) as usize,
+                (point.y - self.y.0) as usize,
+                (point.z - self.z.0) as usize,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the shape of this box in a format that `ndarray` will accept.
+    ///
+    /// NOTE: shape order is (z, y, x), not (x, y, z).
+    pub fn get_shape(&self) -> (usize, usize, usize) {
+        (
+            (self.z.1 - self.z.0) as usize,
+            (self.y.1 - self.y.0) as usize,
+            (self.x.1 - self.x.0) as usize,
+        )
+    }
+
+    /// Gets the number of cells as a vector.
+    pub fn get_num_cells(&self) -> Vector3<usize> {
+        let shape = self.get_shape();
+        Vector3::new(shape.2, shape.1, shape.0)
+    }
+
+    /// Gets the intersection of self with other, returning `None` if the two do not intersect.
+    pub fn intersect(&self, other: &Bounds3) -> Option<Bounds3> {
+        Bounds3::new(
+            (self.x.0.max(other.x.0), self.x.1.min(other.x.1)),
+            (self.y.0.max(other.y.0), self.y.1.min(other.y.1)),
+            (self.z.0.max(other.z.0), self.z.1.min(other.z.1)),
+        )
+        .ok()
+    }
+
+    /// Get the union of `self` with `other`, effectively this is the axis aligned bounding box of
+    /// `self` and `other`.
+    ///
+    /// If both bounds are empty this bound will be empty.
+    pub fn union(&self, other: &Bounds3) -> Bounds3 {
+        Bounds3::new(
+            (self.x.0.min(other.x.0), self.x.1.max(other.x.1)),
+            (self.y.0.min(other.y.0), self.y.1.max(other.y.1)),
+            (self.z.0.min(other.z.0), self.z.1.max(other.z.1)),
+        )
+        .unwrap_or_default()
+    }
+
+    /// Gets the slice of other within self, cropping other so it fits within self.
+    ///
+    /// Note that slices are a pair of (min, max) half-open bounds that describe the slice into an
+    /// array, i.e. they are indices.
+    pub fn get_slice_of_other(&self, other: &Bounds3) -> Option<Vector3<(usize, usize)>> {
+        // First get intersection of the two bounds in the origin frame
+        let intersect = self.intersect(other)?;
+
+        // Rebase the intersection to be a slice relative to the start of self, i.e. subtract the
+        // min bound on each axis from both min and max of the intersection
+        Some(Vector3::new(
+            (
+                (intersect.x.0 - self.x.0) as usize,
+                (intersect.x.1 - self.x.0) as usize,
+            ),
+            (
+                (intersect.y.0 - self.y.0) as usize,
+                (intersect.y.1 - self.y.0) as usize,
+            ),
+            (
+                (intersect.z.0 - self.z.0) as usize,
+                (intersect.z.1 - self.z.0) as usize,
+            ),
+        ))
+    }
+}
+
+impl Default for Bounds3 {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
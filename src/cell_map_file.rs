@@ -4,11 +4,13 @@
 // IMPORTS
 // ------------------------------------------------------------------------------------------------
 
+use std::convert::TryFrom;
+
 use nalgebra::{Affine2, Vector2};
 use ndarray::Array2;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{CellMap, CellMapError, CellMapParams, Layer};
+use crate::{cell_map::Bounds, CellMap, CellMapParams, Error, Layer};
 
 // ------------------------------------------------------------------------------------------------
 // STRUCTS
@@ -32,6 +34,17 @@ where
     /// Number of cells in each layer of the map, in the `x` and `y` map-frame directions.
     pub num_cells: Vector2<usize>,
 
+    /// The ring buffer offset the map's data was stored at, i.e.
+    /// [`CellMapMetadata::start_index`](crate::map_metadata::CellMapMetadata::start_index).
+    ///
+    /// Persisting this is what lets a map that's been scrolled by [`CellMap::move_by`]/
+    /// [`CellMap::move_to`] round-trip correctly -- without it every deserialised map would come
+    /// back with its ring buffer silently reset to an unscrolled state.
+    ///
+    /// [`CellMap::move_by`]: crate::CellMap::move_by
+    /// [`CellMap::move_to`]: crate::CellMap::move_to
+    pub start_index: Vector2<usize>,
+
     /// The size of each cell in the map, in parent-frame units.
     pub cell_size: Vector2<f64>,
 
@@ -60,16 +73,52 @@ where
     L: Layer,
 {
     /// Converts this file into a [`CellMap`].
-    pub fn into_cell_map(self) -> Result<CellMap<L, T>, CellMapError> {
+    ///
+    /// This is a thin wrapper around the [`TryFrom`] impl below.
+    pub fn into_cell_map(self) -> Result<CellMap<L, T>, Error> {
+        CellMap::try_from(self)
+    }
+}
+
+impl<L, T> TryFrom<CellMapFile<L, T>> for CellMap<L, T>
+where
+    L: Layer,
+{
+    type Error = Error;
+
+    fn try_from(file: CellMapFile<L, T>) -> Result<Self, Self::Error> {
+        // Check that we have the number of layers the caller's `L` expects.
+        if file.data.len() != L::NUM_LAYERS {
+            return Err(Error::WrongNumberOfLayers(L::NUM_LAYERS, file.data.len()));
+        }
+
+        // Check that every layer's array actually has the shape the file claims it does.
+        for layer_data in &file.data {
+            let shape = layer_data.shape();
+            let layer_cells = Vector2::new(shape[1], shape[0]);
+
+            if layer_cells != file.num_cells {
+                return Err(Error::LayerWrongShape(layer_cells, file.num_cells));
+            }
+        }
+
+        let cell_bounds = Bounds::new(
+            (0, file.num_cells.x as isize),
+            (0, file.num_cells.y as isize),
+        )?;
+
         let params = CellMapParams {
-            cell_size: self.cell_size,
-            num_cells: self.num_cells,
-            rotation_in_parent_rad: self.from_parent_angle_rad,
-            position_in_parent: self.from_parent_translation,
-            cell_boundary_precision: self.cell_boundary_precision,
+            cell_size: file.cell_size,
+            cell_bounds,
+            rotation_in_parent_rad: file.from_parent_angle_rad,
+            position_in_parent: file.from_parent_translation,
+            cell_boundary_precision: file.cell_boundary_precision,
         };
 
-        CellMap::new_from_data(params, self.data)
+        let mut map = CellMap::new_from_data(params, file.data)?;
+        map.metadata.start_index = file.start_index;
+
+        Ok(map)
     }
 }
 
@@ -83,6 +132,7 @@ where
             num_layers: L::NUM_LAYERS,
             layers: L::all(),
             num_cells: map.metadata.num_cells,
+            start_index: map.metadata.start_index,
             cell_size: map.metadata.cell_size,
             cell_boundary_precision: map.metadata.cell_boundary_precision,
             from_parent_angle_rad: map.params.rotation_in_parent_rad,
@@ -101,10 +151,7 @@ where
     /// Writes the [`CellMapFile`] to the given path, overwriting any existing file. The format of
     /// the written file is JSON.
     #[cfg(feature = "json")]
-    pub fn write_json<P: AsRef<std::path::Path>>(
-        &self,
-        path: P,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn write_json<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
         let file = std::fs::OpenOptions::new()
             .create(true)
             .append(false)
@@ -125,12 +172,378 @@ where
 {
     /// Loads a [`CellMapFile`] from the given path, which points to a JSON file.
     #[cfg(feature = "json")]
-    pub fn from_json<P: AsRef<std::path::Path>>(
-        path: P,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_json<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
         // Open the file
         let file = std::fs::File::open(path)?;
         let map_file: CellMapFile<L, T> = serde_json::from_reader(&file)?;
         Ok(map_file)
     }
 }
+
+impl<L, T> CellMapFile<L, T>
+where
+    L: Layer + Serialize,
+    T: Serialize,
+{
+    /// Writes the [`CellMapFile`] to the given path, overwriting any existing file, using a
+    /// compact binary `bincode` encoding.
+    ///
+    /// This is both faster and much more compact than [`CellMapFile::write_json`], at the cost of
+    /// not being human-readable, which matters for large maps of floating point data.
+    #[cfg(feature = "bincode")]
+    pub fn write_bincode<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(false)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+
+        bincode::serialize_into(file, &self)?;
+
+        Ok(())
+    }
+}
+
+impl<L, T> CellMapFile<L, T>
+where
+    L: Layer + DeserializeOwned,
+    T: DeserializeOwned,
+{
+    /// Loads a [`CellMapFile`] from the given path, which points to a file written by
+    /// [`CellMapFile::write_bincode`].
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+        let map_file: CellMapFile<L, T> = bincode::deserialize_from(&file)?;
+        Ok(map_file)
+    }
+}
+
+/// Magic number identifying a `cell-map` mmap-compatible binary file, written at the start of the
+/// header by [`CellMapFile::write_mmap`].
+#[cfg(feature = "mmap")]
+const MMAP_MAGIC: [u8; 4] = *b"CMAP";
+
+/// Current version of the mmap binary file format written by [`CellMapFile::write_mmap`].
+///
+/// Bump this whenever the header or body encoding changes in a way that isn't backwards
+/// compatible, and add a migration path in [`CellMapFile::from_mmap_file`].
+///
+/// Bumped to 2 when [`CellMapFile::start_index`] was added to the body.
+#[cfg(feature = "mmap")]
+const MMAP_VERSION: u32 = 2;
+
+#[cfg(feature = "mmap")]
+impl<L, T> CellMapFile<L, T>
+where
+    L: Layer + Serialize,
+    T: Serialize,
+{
+    /// Writes this [`CellMapFile`] to `path` in `cell-map`'s mmap-compatible binary format: a
+    /// small versioned header (a magic number, a format version, and the body length), followed
+    /// by the `bincode`-encoded map itself.
+    ///
+    /// The resulting file can be opened with [`CellMapFile::from_mmap_file`], which memory-maps
+    /// it rather than reading it all upfront.
+    pub fn write_mmap<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        use std::io::Write;
+
+        let body = bincode::serialize(&self)?;
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(false)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+
+        let mut writer = std::io::BufWriter::new(file);
+        writer.write_all(&MMAP_MAGIC)?;
+        writer.write_all(&MMAP_VERSION.to_le_bytes())?;
+        writer.write_all(&(body.len() as u64).to_le_bytes())?;
+        writer.write_all(&body)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<L, T> CellMapFile<L, T>
+where
+    L: Layer + DeserializeOwned,
+    T: DeserializeOwned,
+{
+    /// Opens a file written by [`CellMapFile::write_mmap`] by memory-mapping it, validating the
+    /// header's magic number and version, then decoding the body.
+    pub fn from_mmap_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+
+        // SAFETY: as with all uses of `memmap2::Mmap`, the caller must not mutate or truncate
+        // the underlying file while the mapping is alive.
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        const HEADER_LEN: usize = MMAP_MAGIC.len() + 4 + 8;
+        if mmap.len() < HEADER_LEN {
+            return Err(Error::BadMmapHeader);
+        }
+
+        if mmap[0..4] != MMAP_MAGIC {
+            return Err(Error::BadMmapMagic);
+        }
+
+        let version = u32::from_le_bytes(mmap[4..8].try_into().unwrap());
+        if version != MMAP_VERSION {
+            return Err(Error::UnsupportedMmapVersion(version));
+        }
+
+        let body_len = u64::from_le_bytes(mmap[8..HEADER_LEN].try_into().unwrap()) as usize;
+        let body = mmap
+            .get(HEADER_LEN..HEADER_LEN + body_len)
+            .ok_or(Error::BadMmapHeader)?;
+
+        Ok(bincode::deserialize(body)?)
+    }
+}
+
+impl<L, T> CellMapFile<L, T>
+where
+    L: Layer,
+    T: Clone,
+{
+    /// Flattens `layer` into a row-major occupancy-grid payload matching the de-facto
+    /// `nav_msgs/OccupancyGrid` layout, so the map can be handed to standard ROS-style mapping
+    /// tools.
+    ///
+    /// `to_cost` converts each cell's value into a cost in `[0, 100]`, or `-1` for unknown, since
+    /// cell payloads in a [`CellMap`] are generic.
+    ///
+    /// [`CellMap`]: crate::CellMap
+    pub fn to_occupancy_grid<F>(&self, layer: L, to_cost: F) -> OccupancyGrid
+    where
+        F: Fn(&T) -> i8,
+    {
+        let layer_data = &self.data[layer.to_index()];
+        let (height, width) = layer_data.dim();
+        let start = self.start_index;
+
+        // Walk the cells in logical order, undoing the ring buffer offset of a scrolled map.
+        OccupancyGrid {
+            data: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (y, x)))
+                .map(|(y, x)| to_cost(&layer_data[((y + start.y) % height, (x + start.x) % width)]))
+                .collect(),
+            width,
+            height,
+            resolution: self.cell_size.x,
+            origin_position: self.from_parent_translation,
+            origin_angle_rad: self.from_parent_angle_rad,
+        }
+    }
+}
+
+/// A flattened, row-major occupancy-grid payload, matching the de-facto `nav_msgs/OccupancyGrid`
+/// layout used throughout ROS-based robotics tooling.
+///
+/// Produced by [`CellMapFile::to_occupancy_grid`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancyGrid {
+    /// Row-major cell costs in `[0, 100]`, or `-1` for unknown.
+    pub data: Vec<i8>,
+
+    /// The width of the grid, in cells.
+    pub width: usize,
+
+    /// The height of the grid, in cells.
+    pub height: usize,
+
+    /// The size of each cell, in parent-frame units.
+    pub resolution: f64,
+
+    /// The position of the grid's origin in the parent frame.
+    pub origin_position: Vector2<f64>,
+
+    /// The rotation of the grid's frame relative to the parent frame, in radians.
+    pub origin_angle_rad: f64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// TESTS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Vector2;
+
+    use super::*;
+    use crate::{cell_map::Bounds, test_utils::TestLayers, CellMap, CellMapParams};
+
+    /// Builds a small map with distinct per-cell values, so round-tripping it through a
+    /// [`CellMapFile`] can be checked cell-by-cell rather than just by shape.
+    fn test_map() -> CellMap<TestLayers, f64> {
+        let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+            CellMapParams {
+                cell_bounds: Bounds::new((0, 3), (0, 2)).unwrap(),
+                cell_size: Vector2::new(1.0, 1.0),
+                ..Default::default()
+            },
+            0.0,
+        );
+
+        for ((layer, idx), v) in map.iter_mut().indexed() {
+            *v = (layer.to_index() * 100 + idx.y * 10 + idx.x) as f64;
+        }
+
+        map
+    }
+
+    /// As [`test_map`], but scrolled by [`CellMap::move_by`] first, so its data is backed by a
+    /// non-trivial ring buffer offset -- round-tripping this through a [`CellMapFile`] needs
+    /// `start_index` itself to survive, not just the cell values.
+    fn scrolled_test_map() -> CellMap<TestLayers, f64> {
+        let mut map = test_map();
+        map.move_by(Vector2::new(1, 1), -1.0);
+        map
+    }
+
+    /// Returns a unique path under the system temp dir for a round-trip test to write to.
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "cell-map-test-{}-{}-{:?}",
+            name,
+            std::process::id(),
+            std::thread::current().id()
+        ))
+    }
+
+    #[test]
+    fn cell_map_file_round_trips_through_cell_map() {
+        let map = test_map();
+        let file = CellMapFile::new(&map);
+        let round_tripped = file.into_cell_map().unwrap();
+
+        for ((layer, idx), &v) in map.iter().indexed() {
+            assert_eq!(*round_tripped.get(layer, idx).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn cell_map_file_round_trips_a_scrolled_map() {
+        let map = scrolled_test_map();
+        let file = CellMapFile::new(&map);
+        let round_tripped = file.into_cell_map().unwrap();
+
+        assert_eq!(round_tripped.metadata.start_index, map.metadata.start_index);
+
+        for ((layer, idx), &v) in map.iter().indexed() {
+            assert_eq!(*round_tripped.get(layer, idx).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn cell_map_file_rejects_wrong_number_of_layers() {
+        let mut file = CellMapFile::new(&test_map());
+        file.data.pop();
+        file.layers.pop();
+
+        assert!(matches!(
+            CellMap::try_from(file),
+            Err(Error::WrongNumberOfLayers(3, 2))
+        ));
+    }
+
+    #[test]
+    fn cell_map_file_rejects_layer_with_wrong_shape() {
+        let mut file = CellMapFile::new(&test_map());
+        file.data[0] = Array2::from_elem((1, 1), 0.0);
+
+        assert!(matches!(
+            CellMap::try_from(file),
+            Err(Error::LayerWrongShape(..))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn bincode_round_trips_through_a_file() {
+        let path = temp_path("bincode");
+        let map = test_map();
+
+        CellMapFile::new(&map).write_bincode(&path).unwrap();
+        let file: CellMapFile<TestLayers, f64> = CellMapFile::from_bincode(&path).unwrap();
+        let round_tripped = file.into_cell_map().unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        for ((layer, idx), &v) in map.iter().indexed() {
+            assert_eq!(*round_tripped.get(layer, idx).unwrap(), v);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn mmap_round_trips_through_a_file() {
+        let path = temp_path("mmap");
+        let map = test_map();
+
+        CellMapFile::new(&map).write_mmap(&path).unwrap();
+        let file: CellMapFile<TestLayers, f64> = CellMapFile::from_mmap_file(&path).unwrap();
+        let round_tripped = file.into_cell_map().unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        for ((layer, idx), &v) in map.iter().indexed() {
+            assert_eq!(*round_tripped.get(layer, idx).unwrap(), v);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn mmap_rejects_bad_magic() {
+        let path = temp_path("mmap-bad-magic");
+
+        CellMapFile::new(&test_map()).write_mmap(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] = b'X';
+        std::fs::write(&path, &bytes).unwrap();
+
+        let result = CellMapFile::<TestLayers, f64>::from_mmap_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::BadMmapMagic)));
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn mmap_rejects_unsupported_version() {
+        let path = temp_path("mmap-bad-version");
+
+        CellMapFile::new(&test_map()).write_mmap(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[4..8].copy_from_slice(&99u32.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+
+        let result = CellMapFile::<TestLayers, f64>::from_mmap_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::UnsupportedMmapVersion(99))));
+    }
+
+    #[test]
+    fn to_occupancy_grid_flattens_row_major() {
+        let map = test_map();
+        let file = CellMapFile::new(&map);
+
+        let grid = file.to_occupancy_grid(TestLayers::Layer0, |&v| v as i8);
+
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 2);
+        assert_eq!(
+            grid.data,
+            vec![0, 1, 2, 10, 11, 12]
+                .iter()
+                .map(|&v| v as i8)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Chunked, sparse storage for layers that grow unpredictably as a robot explores.
+//!
+//! [`CellMap`]'s default storage (a `Vec<Array2<T>>`) is dense: every call to
+//! [`CellMap::resize`] reallocates and copies every layer's entire array, which gets expensive
+//! once a map is large and only sparsely touched. [`ChunkedLayer`] is an alternative backend for
+//! a single layer that instead stores fixed-size tiles in a `HashMap<(i32, i32), Array2<T>>`,
+//! keyed by tile coordinate. A cell index maps to a tile coordinate plus a local offset within
+//! that tile; reading a cell in a tile that's never been written to returns the layer's default
+//! value without allocating anything, and writing to a new cell lazily allocates only the one
+//! tile it falls in. Growing the map's extent therefore touches `O(1)` tiles rather than
+//! recopying the whole layer.
+//!
+//! **Note:** this is currently a standalone storage building block, used on its own rather than
+//! plugged into [`CellMap`] as a second backend: [`CellMap`] and its iterators are written
+//! directly against `Vec<Array2<T>>`, so making [`CellMap`] generic over the storage backend
+//! (dense vs. chunked) would mean threading a storage trait through every iterator, slicer and
+//! the `Index`/`IndexMut` impls. That's a bigger, separate piece of work; this gives callers who
+//! need sparse, incrementally-growing storage somewhere to start.
+//!
+//! [`CellMap`]: crate::CellMap
+//! [`CellMap::resize`]: crate::CellMap::resize
+
+// ------------------------------------------------------------------------------------------------
+// IMPORTS
+// ------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use nalgebra::Point2;
+use ndarray::Array2;
+
+use crate::Bounds;
+
+// ------------------------------------------------------------------------------------------------
+// CONSTS
+// ------------------------------------------------------------------------------------------------
+
+/// Side length, in cells, of each tile allocated by a [`ChunkedLayer`].
+pub const TILE_SIZE: usize = 64;
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A single sparse, tiled layer, storing cells in fixed-size `TILE_SIZE x TILE_SIZE` tiles
+/// allocated only once a cell inside them is written to.
+///
+/// See the [module-level docs](self) for the motivation and design.
+#[derive(Debug, Clone)]
+pub struct ChunkedLayer<T> {
+    tiles: HashMap<(i32, i32), Array2<T>>,
+    default: T,
+}
+
+// ------------------------------------------------------------------------------------------------
+// IMPLS
+// ------------------------------------------------------------------------------------------------
+
+impl<T> ChunkedLayer<T>
+where
+    T: Clone,
+{
+    /// Creates a new, empty [`ChunkedLayer`], which reads as `default` everywhere until cells are
+    /// written to.
+    pub fn new(default: T) -> Self {
+        Self {
+            tiles: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Splits a global cell index into the tile it falls in, and the local `(x, y)` offset within
+    /// that tile.
+    fn tile_key_and_offset(index: Point2<isize>) -> ((i32, i32), (usize, usize)) {
+        let tile_size = TILE_SIZE as isize;
+        let tile_x = index.x.div_euclid(tile_size);
+        let tile_y = index.y.div_euclid(tile_size);
+        let local_x = index.x.rem_euclid(tile_size) as usize;
+        let local_y = index.y.rem_euclid(tile_size) as usize;
+
+        ((tile_x as i32, tile_y as i32), (local_x, local_y))
+    }
+
+    /// Gets the value at `index`, returning a reference to the layer's default value if `index`
+    /// falls inside a tile that's never been written to.
+    ///
+    /// This never allocates.
+    pub fn get(&self, index: Point2<isize>) -> &T {
+        let (key, (x, y)) = Self::tile_key_and_offset(index);
+
+        match self.tiles.get(&key) {
+            Some(tile) => &tile[(y, x)],
+            None => &self.default,
+        }
+    }
+
+    /// Sets the value at `index`, lazily allocating the tile it falls in if this is the first
+    /// write to that tile.
+    pub fn set(&mut self, index: Point2<isize>, value: T) {
+        let (key, (x, y)) = Self::tile_key_and_offset(index);
+        let default = self.default.clone();
+        let tile = self
+            .tiles
+            .entry(key)
+            .or_insert_with(|| Array2::from_elem((TILE_SIZE, TILE_SIZE), default));
+
+        tile[(y, x)] = value;
+    }
+
+    /// Returns the number of tiles currently allocated.
+    pub fn num_allocated_tiles(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns the [`Bounds`], in cell indices, of the union of every currently-allocated tile.
+    ///
+    /// This is the chunked equivalent of [`CellMap::cell_bounds`]: it only reports the extent of
+    /// tiles that have actually been touched, not some fixed a-priori size.
+    ///
+    /// [`CellMap::cell_bounds`]: crate::CellMap::cell_bounds
+    pub fn cell_bounds(&self) -> Option<Bounds> {
+        let tile_size = TILE_SIZE as isize;
+
+        self.tiles.keys().fold(None, |bounds, &(tx, ty)| {
+            let tile_bounds = Bounds {
+                x: (tx as isize * tile_size, (tx as isize + 1) * tile_size),
+                y: (ty as isize * tile_size, (ty as isize + 1) * tile_size),
+            };
+
+            Some(match bounds {
+                Some(b) => b.union(&tile_bounds),
+                None => tile_bounds,
+            })
+        })
+    }
+
+    /// Merges `other` into `self`, pushing every cell of `other` directly into the corresponding
+    /// tile of `self` via `func`.
+    ///
+    /// `func` is given the current value in `self` (or the default, if unwritten) and the value
+    /// from `other`, and returns the new value to store in `self`. Only tiles that `other` has
+    /// allocated are visited, so merging a sparsely-populated `other` only touches the tiles it
+    /// actually touched.
+    pub fn merge<F>(&mut self, other: &ChunkedLayer<T>, func: F)
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        let tile_size = TILE_SIZE as isize;
+
+        for (&(tx, ty), tile) in other.tiles.iter() {
+            for ((local_y, local_x), other_value) in tile.indexed_iter() {
+                let index = Point2::new(
+                    tx as isize * tile_size + local_x as isize,
+                    ty as isize * tile_size + local_y as isize,
+                );
+
+                let merged = func(self.get(index), other_value);
+                self.set(index, merged);
+            }
+        }
+    }
+
+    /// Drops every allocated tile whose cells are all still equal to the layer's default value.
+    ///
+    /// Borrowed from zvault's approach to compacting sparse chunk stores: after a merge or a
+    /// round of edits that end up reverting cells back to their default, this reclaims memory
+    /// from tiles that no longer hold any real data, without the caller needing to track which
+    /// tiles became empty.
+    pub fn compact(&mut self)
+    where
+        T: PartialEq,
+    {
+        let default = &self.default;
+        self.tiles
+            .retain(|_, tile| tile.iter().any(|value| value != default));
+    }
+}
@@ -0,0 +1,242 @@
+//! Provides [`DynamicLayer`], a [`Layer`] implementation whose set of layers is decided at
+//! runtime rather than fixed by an enum at compile time.
+//!
+//! [`Layer`]'s usual contract (enforced by `#[derive(Layer)]`) is that every possible index has a
+//! corresponding variant, which is what lets [`CellMap`] treat `L::NUM_LAYERS` as the map's fixed
+//! layer count. That doesn't fit pipelines where the layer set is data-driven, e.g. accumulating
+//! one layer per incoming sensor frame. [`DynamicLayer`] instead interns a name in a shared,
+//! process-wide registry and carries around the resulting index; [`CellMap::add_layer`] and
+//! [`CellMap::remove_layer`] grow and shrink a [`CellMap<DynamicLayer, _>`]'s storage to match.
+//!
+//! Because the registry is shared across every [`CellMap<DynamicLayer, _>`] in the process, a
+//! layer interned by one map is visible (by name) to all of them, but each map independently
+//! tracks which of those layers it actually has storage for; indexing a map with a
+//! [`DynamicLayer`] it hasn't called [`CellMap::add_layer`] for panics, just as indexing a normal
+//! [`Layer`] with an out-of-range enum variant never happens. Use [`Layer::try_from_index`] rather
+//! than [`Layer::from_index`] when iterating over indices that might not be present.
+//!
+//! [`CellMap`]: crate::CellMap
+//! [`CellMap<DynamicLayer, _>`]: crate::CellMap
+//! [`CellMap::add_layer`]: crate::CellMap::add_layer
+//! [`CellMap::remove_layer`]: crate::CellMap::remove_layer
+
+// ------------------------------------------------------------------------------------------------
+// IMPORTS
+// ------------------------------------------------------------------------------------------------
+
+use std::sync::{Mutex, OnceLock};
+
+use ndarray::Array2;
+
+use crate::{cell_map::CellMap, Error, Layer};
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A [`Layer`] backed by a process-wide string registry rather than a fixed enum.
+///
+/// Clone and copy are cheap: a [`DynamicLayer`] is just an interned index, with the name looked up
+/// from the registry on demand via [`DynamicLayer::name`].
+///
+/// [`Layer::NUM_LAYERS`] and [`Layer::FIRST`] have no real meaning for [`DynamicLayer`] (there's no
+/// fixed count or first layer), so they're set to `0` and index `0` (whichever name was interned
+/// in the registry first, if any) respectively; don't rely on either of those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynamicLayer {
+    index: u16,
+}
+
+// ------------------------------------------------------------------------------------------------
+// IMPLS
+// ------------------------------------------------------------------------------------------------
+
+fn registry() -> &'static Mutex<Vec<Option<String>>> {
+    static REGISTRY: OnceLock<Mutex<Vec<Option<String>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+impl DynamicLayer {
+    /// Interns `name` in the shared registry (if it isn't already present) and returns the
+    /// [`DynamicLayer`] handle for it.
+    pub fn new(name: &str) -> Self {
+        let mut registry = registry().lock().unwrap();
+
+        if let Some(index) = registry.iter().position(|slot| slot.as_deref() == Some(name)) {
+            return Self {
+                index: index as u16,
+            };
+        }
+
+        // `CellMap::remove_layer` only frees a map's own storage for a layer, never the shared
+        // registry entry itself (the name stays valid for any other map that already holds a
+        // handle to it), so there's never a freed slot here to reuse.
+        registry.push(Some(name.to_owned()));
+        let index = registry.len() - 1;
+
+        Self {
+            index: index as u16,
+        }
+    }
+
+    /// Returns the name this layer was interned with.
+    pub fn name(&self) -> String {
+        registry().lock().unwrap()[self.index as usize]
+            .clone()
+            .expect("DynamicLayer's registry entry was unexpectedly empty")
+    }
+}
+
+impl Layer for DynamicLayer {
+    const NUM_LAYERS: usize = 0;
+
+    const FIRST: Self = DynamicLayer { index: 0 };
+
+    fn to_index(&self) -> usize {
+        self.index as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self::try_from_index(index)
+            .unwrap_or_else(|| panic!("Got a layer index of {} but no DynamicLayer is registered at it", index))
+    }
+
+    fn all() -> Vec<Self> {
+        registry()
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                slot.as_ref().map(|_| DynamicLayer {
+                    index: index as u16,
+                })
+            })
+            .collect()
+    }
+
+    fn try_from_index(index: usize) -> Option<Self> {
+        match registry().lock().unwrap().get(index) {
+            Some(Some(_)) => Some(DynamicLayer {
+                index: index as u16,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<T> CellMap<DynamicLayer, T>
+where
+    T: Clone + Default,
+{
+    /// Interns `name` as a layer (if it isn't already) and ensures this map has storage for it,
+    /// filling it with `fill`. If `name` already has storage in this map, that storage is
+    /// replaced.
+    ///
+    /// Returns the [`DynamicLayer`] handle for `name`, which can then be used to index the map as
+    /// normal.
+    pub fn add_layer(&mut self, name: &str, fill: T) -> DynamicLayer {
+        let layer = DynamicLayer::new(name);
+        let index = layer.to_index();
+
+        if index >= self.data.len() {
+            self.data.resize(index + 1, Array2::from_elem((0, 0), T::default()));
+        }
+
+        self.data[index] = Array2::from_elem(self.metadata.cell_bounds.get_shape(), fill);
+
+        layer
+    }
+
+    /// Removes `name`'s layer from this map, freeing its storage.
+    ///
+    /// This only affects this map's storage: `name` remains interned in the shared registry, so
+    /// other maps that already hold a [`DynamicLayer`] handle for it are unaffected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownLayer`] if this map has no storage for `name` (it was never added,
+    /// or has already been removed).
+    pub fn remove_layer(&mut self, name: &str) -> Result<(), Error> {
+        let index = DynamicLayer::new(name).to_index();
+
+        if index >= self.data.len() || self.data[index].is_empty() {
+            return Err(Error::UnknownLayer(name.to_owned()));
+        }
+
+        self.data[index] = Array2::from_elem((0, 0), T::default());
+
+        // Shrink the backing Vec if we just freed the highest-indexed layer(s).
+        while matches!(self.data.last(), Some(layer) if layer.is_empty()) {
+            self.data.pop();
+        }
+
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// TESTS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Vector2;
+
+    use super::*;
+    use crate::{cell_map::Bounds, CellMapParams};
+
+    /// Returns a name unique to this test run, so tests that intern names into the shared,
+    /// process-wide registry don't collide with each other or with names left behind by previous
+    /// runs of the same test binary.
+    fn unique_name(tag: &str) -> String {
+        format!(
+            "dynamic-layer-test-{}-{}-{:?}",
+            tag,
+            std::process::id(),
+            std::thread::current().id()
+        )
+    }
+
+    fn test_params() -> CellMapParams {
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 2), (0, 2)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn iter_skips_a_layer_interned_by_another_map() {
+        // `DynamicLayer::all()` is a process-wide view of every name ever interned by any
+        // `CellMap<DynamicLayer, _>`, so `map_a` interning a name that `map_b` never adds must
+        // not cause `map_b`'s iterators to panic trying to index storage it doesn't have.
+        let mut map_a = CellMap::<DynamicLayer, f64>::new(test_params());
+        map_a.add_layer(&unique_name("a-only"), 1.0);
+
+        let mut map_b = CellMap::<DynamicLayer, f64>::new(test_params());
+        map_b.add_layer(&unique_name("b-only"), 2.0);
+
+        let values: Vec<f64> = map_b.iter().copied().collect();
+        assert_eq!(values, vec![2.0; 4]);
+
+        for v in map_b.iter_mut() {
+            *v *= 10.0;
+        }
+
+        let values: Vec<f64> = map_b.iter().copied().collect();
+        assert_eq!(values, vec![20.0; 4]);
+    }
+
+    #[test]
+    fn iter_skips_a_removed_layer() {
+        let mut map = CellMap::<DynamicLayer, f64>::new(test_params());
+        map.add_layer(&unique_name("removed"), 1.0);
+        map.add_layer(&unique_name("kept"), 2.0);
+
+        map.remove_layer(&unique_name("removed")).unwrap();
+
+        let values: Vec<f64> = map.iter().copied().collect();
+        assert_eq!(values, vec![2.0; 4]);
+    }
+}
@@ -4,6 +4,8 @@
 
 use nalgebra::{Point2, Vector2};
 
+use crate::cell_map::{Bounds, Bounds3};
+
 // ------------------------------------------------------------------------------------------------
 // ENUMS
 // ------------------------------------------------------------------------------------------------
@@ -13,13 +15,18 @@ use nalgebra::{Point2, Vector2};
 /// [`CellMap`]: crate::CellMap
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    /// Error returned when trying to construct a [`Windows`] slicer using a `semi_width` which
-    /// would create a window larger than the size of the map.
+    /// Error returned when trying to construct a [`Windows`] slicer using a window (first) which
+    /// would be larger than the size of the map (second).
     ///
     /// [`Windows`]: crate::iterators::slicers::Windows
     #[error("Can't create a Windows iterator since the window size ({0}) is larger than the map size ({1})")]
     WindowLargerThanMap(Vector2<usize>, Vector2<usize>),
 
+    /// The given anchor (first) doesn't lie inside the given window extent (second), so there's
+    /// no valid offset for the "current" cell within the window.
+    #[error("Window anchor {0} is outside its window extent {1}")]
+    InvalidWindowAnchor(Vector2<usize>, Vector2<usize>),
+
     /// The given parent-frame position (name, first element) is outside the map.
     #[error("Parent-frame position {0} ({1}) is outside the map")]
     PositionOutsideMap(String, Point2<f64>),
@@ -32,6 +39,58 @@ pub enum Error {
     #[error("Expected {0} cells in layer, but found {1}")]
     LayerWrongShape(Vector2<usize>, Vector2<usize>),
 
+    /// The given index is outside the bounds of the map.
+    #[error("Index {0} is outside the map")]
+    IndexOutsideMap(Point2<usize>),
+
+    /// The given [`Bounds`] are invalid, i.e. the minimum is larger than the maximum on at least
+    /// one axis.
+    ///
+    /// [`Bounds`]: crate::cell_map::Bounds
+    #[error("Bounds {0:?} are invalid")]
+    InvalidBounds(Bounds),
+
+    /// The given [`Bounds3`] are invalid, i.e. the minimum is larger than the maximum on at least
+    /// one axis.
+    ///
+    /// [`Bounds3`]: crate::cell_map::Bounds3
+    #[error("Bounds3 {0:?} are invalid")]
+    InvalidBounds3(Bounds3),
+
+    /// Resolving a [`Bound<isize>`](std::ops::Bound) pair into a [`Bounds`] overflowed `isize`.
+    ///
+    /// [`Bounds`]: crate::cell_map::Bounds
+    #[error("Resolving a Bound<isize> pair into Bounds overflowed isize")]
+    BoundsOverflow,
+
+    /// The named [`DynamicLayer`] has no storage in this map.
+    ///
+    /// [`DynamicLayer`]: crate::DynamicLayer
+    #[error("No layer named \"{0}\" exists in this map")]
+    UnknownLayer(String),
+
+    /// A [`Polygon`] slicer was constructed with fewer than 3 vertices.
+    ///
+    /// [`Polygon`]: crate::iterators::slicers::Polygon
+    #[error("A Polygon slicer needs at least 3 vertices, got {0}")]
+    PolygonTooFewVertices(usize),
+
+    /// A [`Windows`] slicer (or the parallel `CellMap::par_window_iter`) was constructed on a map
+    /// whose [`CellMapMetadata::start_index`] is non-zero, i.e. one that has been scrolled by
+    /// [`CellMap::move_by`]/[`CellMap::move_to`] at some point in the past. Neither can express a
+    /// window that straddles the ring buffer's wrap point as a single contiguous view, so rather
+    /// than silently returning wrongly-wrapped data, construction is refused until the map is
+    /// rebuilt (or otherwise has its `start_index` reset to zero). [`PaddedWindows`] copies each
+    /// window and so works on scrolled maps.
+    ///
+    /// [`Windows`]: crate::iterators::slicers::Windows
+    /// [`PaddedWindows`]: crate::iterators::slicers::PaddedWindows
+    /// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+    /// [`CellMap::move_by`]: crate::CellMap::move_by
+    /// [`CellMap::move_to`]: crate::CellMap::move_to
+    #[error("Can't create a windowed iterator over a map that has been scrolled (start_index = {0}); rebuild the map or reset its start_index first")]
+    WindowedIterOnScrolledMap(Vector2<usize>),
+
     /// Errors associated with `std::io` operations.
     #[error("An IO error occured: {0}")]
     IoError(std::io::Error),
@@ -40,4 +99,45 @@ pub enum Error {
     #[cfg(feature = "json")]
     #[error("Error in serde_json: {0}")]
     JsonError(serde_json::Error),
+
+    /// Errors associated with `bincode` operations.
+    #[cfg(feature = "bincode")]
+    #[error("Error in bincode: {0}")]
+    BincodeError(bincode::Error),
+
+    /// An mmap-backed file was too short to contain a valid header.
+    #[cfg(feature = "mmap")]
+    #[error("File is too short to contain a valid cell-map mmap header")]
+    BadMmapHeader,
+
+    /// An mmap-backed file didn't start with the expected magic number.
+    #[cfg(feature = "mmap")]
+    #[error("File does not start with the cell-map mmap magic number")]
+    BadMmapMagic,
+
+    /// An mmap-backed file was written with a format version this version of the crate doesn't
+    /// understand.
+    #[cfg(feature = "mmap")]
+    #[error("Unsupported cell-map mmap file version {0}")]
+    UnsupportedMmapVersion(u32),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IoError(e)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::JsonError(e)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl From<bincode::Error> for Error {
+    fn from(e: bincode::Error) -> Self {
+        Error::BincodeError(e)
+    }
 }
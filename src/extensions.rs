@@ -53,7 +53,9 @@ impl Point2Ext for Point2<usize> {
 
 impl Affine2Ext for Affine2<f64> {
     fn position(&self, index: Point2<usize>) -> Point2<f64> {
-        // Get the centre of the cell, which is + 0.5 cells in the x and y direction.
+        // Get the centre of the cell, which is + 0.5 cells in the x and y direction. `self`
+        // already encodes the map's cell_size scale, rotation_in_parent_rad, and
+        // position_in_parent, so transforming this local centre gives the parent-frame position.
         let index_centre = index.cast() + Vector2::new(0.5, 0.5);
         self.transform_point(&index_centre)
     }
@@ -66,6 +66,35 @@ where
     pub(crate) to: L,
 }
 
+/// Produces data cell-major: for each cell index, every selected layer's value at that cell, in
+/// the order the layers were selected (`Layer::to_index()` order when every layer is selected).
+///
+/// This is the cell-major sibling of [`Many`], which is layer-major (it exhausts one layer's cells
+/// before moving to the next). [`Stacked`] instead exhausts one cell's layers before moving to the
+/// next cell, letting you read a full column of aligned layer values at one position without
+/// separate passes or manual indexing.
+#[derive(Debug, Clone)]
+pub struct Stacked<L>
+where
+    L: Layer,
+{
+    pub(crate) layers: Vec<L>,
+}
+
+/// Produces, for each cell, all `sources` layer values at that cell alongside a mutable handle to
+/// the `to` layer at the same cell, allowing a derived layer (surface normals, gradient magnitude,
+/// traversability score, ...) to be folded from several input layers in one pass.
+///
+/// `to` must not alias any layer in `sources`.
+#[derive(Debug, Clone)]
+pub struct MapFold<L>
+where
+    L: Layer,
+{
+    pub(crate) sources: VecDeque<L>,
+    pub(crate) to: L,
+}
+
 // ------------------------------------------------------------------------------------------------
 // IMPLS
 // ------------------------------------------------------------------------------------------------
@@ -96,3 +125,24 @@ where
         Some(self.from.clone())
     }
 }
+
+impl<L> Layerer<L> for Stacked<L>
+where
+    L: Layer,
+{
+    fn current(&self) -> Option<L> {
+        self.layers.first().cloned()
+    }
+}
+
+impl<L> Layerer<L> for MapFold<L>
+where
+    L: Layer,
+{
+    fn current(&self) -> Option<L> {
+        self.sources
+            .front()
+            .cloned()
+            .or_else(|| Some(self.to.clone()))
+    }
+}
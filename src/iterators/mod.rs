@@ -47,6 +47,8 @@
 
 pub mod indexed;
 pub mod layerers;
+#[cfg(feature = "rayon")]
+pub mod par_iter;
 pub mod positioned;
 pub mod slicers;
 #[cfg(test)]
@@ -58,9 +60,10 @@ mod tests;
 
 use layerers::*;
 use nalgebra::{Point2, Vector2};
+use ndarray::Array2;
 use slicers::*;
 
-use crate::{CellMap, Error, Layer};
+use crate::{extensions::Point2Ext, CellMap, Error, Layer, OneLayer};
 
 use self::{indexed::Indexed, positioned::Positioned};
 
@@ -115,6 +118,34 @@ where
         }
     }
 
+    pub(crate) fn new_cells_region(
+        map: &'m CellMap<L, T>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> CellMapIter<'m, L, T, Many<L>, Cells> {
+        CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Cells::from_map_region(map, corner_a, corner_b),
+        }
+    }
+
+    pub(crate) fn new_sub_grid(
+        map: &'m CellMap<L, T>,
+        x: impl std::ops::RangeBounds<usize>,
+        y: impl std::ops::RangeBounds<usize>,
+    ) -> CellMapIter<'m, L, T, Many<L>, SubGrid> {
+        CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: SubGrid::from_map(map, x, y),
+        }
+    }
+
     pub(crate) fn new_windows(
         map: &'m CellMap<L, T>,
         semi_width: Vector2<usize>,
@@ -128,6 +159,49 @@ where
         })
     }
 
+    pub(crate) fn new_windows_region(
+        map: &'m CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<CellMapIter<'m, L, T, Many<L>, Windows>, Error> {
+        Ok(CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Windows::from_map_region(map, semi_width, corner_a, corner_b)?,
+        })
+    }
+
+    pub(crate) fn new_windows_asym(
+        map: &'m CellMap<L, T>,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<CellMapIter<'m, L, T, Many<L>, Windows>, Error> {
+        Ok(CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Windows::from_map_asym(map, extent, anchor)?,
+        })
+    }
+
+    pub(crate) fn new_padded_windows(
+        map: &'m CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        padding: WindowPadding,
+    ) -> Result<CellMapIter<'m, L, T, Many<L>, PaddedWindows>, Error> {
+        Ok(CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: PaddedWindows::from_map(map, semi_width, padding)?,
+        })
+    }
+
     pub(crate) fn new_line(
         map: &'m CellMap<L, T>,
         start_position: Point2<f64>,
@@ -142,6 +216,72 @@ where
         })
     }
 
+    pub(crate) fn new_thick_line(
+        map: &'m CellMap<L, T>,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        half_width: f64,
+    ) -> Result<CellMapIter<'m, L, T, Many<L>, ThickLine>, Error> {
+        Ok(CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: ThickLine::from_map::<L, T>(
+                map.metadata,
+                start_position,
+                end_position,
+                half_width,
+            )?,
+        })
+    }
+
+    pub(crate) fn new_polygon(
+        map: &'m CellMap<L, T>,
+        vertices: &[Point2<f64>],
+    ) -> Result<CellMapIter<'m, L, T, Many<L>, Polygon>, Error> {
+        Ok(CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Polygon::from_map::<L, T>(map.metadata, vertices)?,
+        })
+    }
+
+    pub(crate) fn new_disk(
+        map: &'m CellMap<L, T>,
+        center: Point2<f64>,
+        radius: f64,
+        metric: DiskMetric,
+    ) -> CellMapIter<'m, L, T, Many<L>, Disk> {
+        CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Disk::from_map::<L, T>(map.metadata, center, radius, metric),
+        }
+    }
+
+    pub(crate) fn new_wavefront<F>(
+        map: &'m CellMap<L, T>,
+        seeds: Vec<Point2<usize>>,
+        connectivity: WavefrontConnectivity,
+        step_cost: F,
+    ) -> CellMapIter<'m, L, T, Many<L>, Wavefront<F>>
+    where
+        F: Fn(Point2<usize>) -> Option<f64>,
+    {
+        CellMapIter {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Wavefront::from_map::<L, T>(map.metadata, seeds, connectivity, step_cost),
+        }
+    }
+
     /// Converts this iterator to use a [`Single`] layerer, produing data from only one layer.
     pub fn layer(self, layer: L) -> CellMapIter<'m, L, T, Single<L>, S> {
         CellMapIter {
@@ -162,6 +302,28 @@ where
         }
     }
 
+    /// Converts this iterator to use a [`Stacked`] layerer, producing every layer's value for each
+    /// cell before moving to the next cell.
+    pub fn stacked(self) -> CellMapIter<'m, L, T, Stacked<L>, S> {
+        CellMapIter {
+            map: self.map,
+            layerer: Stacked { layers: L::all() },
+            slicer: self.slicer,
+        }
+    }
+
+    /// Converts this iterator to use a [`Stacked`] layerer restricted to the given `layers`,
+    /// producing each selected layer's value for a cell before moving to the next cell.
+    pub fn stacked_layers(self, layers: &[L]) -> CellMapIter<'m, L, T, Stacked<L>, S> {
+        CellMapIter {
+            map: self.map,
+            layerer: Stacked {
+                layers: layers.to_vec(),
+            },
+            slicer: self.slicer,
+        }
+    }
+
     /// Converts this iterator to also produce the index of the iterated item as well as its value.
     pub fn indexed(self) -> CellMapIter<'m, L, T, R, Indexed<'m, L, T, S>> {
         let current_layer = self.layerer.current().unwrap();
@@ -204,6 +366,38 @@ where
         }
     }
 
+    pub(crate) fn new_cells_region(
+        map: &'m mut CellMap<L, T>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> CellMapIterMut<'m, L, T, Many<L>, Cells> {
+        let slicer = Cells::from_map_region(map, corner_a, corner_b);
+
+        CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer,
+        }
+    }
+
+    pub(crate) fn new_sub_grid(
+        map: &'m mut CellMap<L, T>,
+        x: impl std::ops::RangeBounds<usize>,
+        y: impl std::ops::RangeBounds<usize>,
+    ) -> CellMapIterMut<'m, L, T, Many<L>, SubGrid> {
+        let slicer = SubGrid::from_map(map, x, y);
+
+        CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer,
+        }
+    }
+
     pub(crate) fn new_windows(
         map: &'m mut CellMap<L, T>,
         semi_width: Vector2<usize>,
@@ -219,6 +413,55 @@ where
         })
     }
 
+    pub(crate) fn new_windows_region(
+        map: &'m mut CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<CellMapIterMut<'m, L, T, Many<L>, Windows>, Error> {
+        let slicer = Windows::from_map_region(map, semi_width, corner_a, corner_b)?;
+
+        Ok(CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer,
+        })
+    }
+
+    pub(crate) fn new_windows_asym(
+        map: &'m mut CellMap<L, T>,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<CellMapIterMut<'m, L, T, Many<L>, Windows>, Error> {
+        let slicer = Windows::from_map_asym(map, extent, anchor)?;
+
+        Ok(CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer,
+        })
+    }
+
+    pub(crate) fn new_padded_windows(
+        map: &'m mut CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        padding: WindowPadding,
+    ) -> Result<CellMapIterMut<'m, L, T, Many<L>, PaddedWindows>, Error> {
+        let slicer = PaddedWindows::from_map(map, semi_width, padding)?;
+
+        Ok(CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer,
+        })
+    }
+
     pub(crate) fn new_line(
         map: &'m mut CellMap<L, T>,
         start_position: Point2<f64>,
@@ -234,6 +477,76 @@ where
         })
     }
 
+    pub(crate) fn new_thick_line(
+        map: &'m mut CellMap<L, T>,
+        start_position: Point2<f64>,
+        end_position: Point2<f64>,
+        half_width: f64,
+    ) -> Result<CellMapIterMut<'m, L, T, Many<L>, ThickLine>, Error> {
+        let metadata = map.metadata;
+        Ok(CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: ThickLine::from_map::<L, T>(
+                metadata,
+                start_position,
+                end_position,
+                half_width,
+            )?,
+        })
+    }
+
+    pub(crate) fn new_polygon(
+        map: &'m mut CellMap<L, T>,
+        vertices: &[Point2<f64>],
+    ) -> Result<CellMapIterMut<'m, L, T, Many<L>, Polygon>, Error> {
+        let metadata = map.metadata;
+        Ok(CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Polygon::from_map::<L, T>(metadata, vertices)?,
+        })
+    }
+
+    pub(crate) fn new_disk(
+        map: &'m mut CellMap<L, T>,
+        center: Point2<f64>,
+        radius: f64,
+        metric: DiskMetric,
+    ) -> CellMapIterMut<'m, L, T, Many<L>, Disk> {
+        let metadata = map.metadata;
+        CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Disk::from_map::<L, T>(metadata, center, radius, metric),
+        }
+    }
+
+    pub(crate) fn new_wavefront<F>(
+        map: &'m mut CellMap<L, T>,
+        seeds: Vec<Point2<usize>>,
+        connectivity: WavefrontConnectivity,
+        step_cost: F,
+    ) -> CellMapIterMut<'m, L, T, Many<L>, Wavefront<F>>
+    where
+        F: Fn(Point2<usize>) -> Option<f64>,
+    {
+        let metadata = map.metadata;
+        CellMapIterMut {
+            map,
+            layerer: Many {
+                layers: L::all().into(),
+            },
+            slicer: Wavefront::from_map::<L, T>(metadata, seeds, connectivity, step_cost),
+        }
+    }
+
     /// Converts this iterator to use a [`Single`] layerer, produing data from only one layer.
     pub fn layer(self, layer: L) -> CellMapIterMut<'m, L, T, Single<L>, S> {
         CellMapIterMut {
@@ -263,6 +576,73 @@ where
         }
     }
 
+    /// Converts this iterator to use a [`MapFold`] layerer, which folds data from many `sources`
+    /// layers into a single `to` layer, one cell at a time.
+    ///
+    /// `to` must not alias any layer in `sources`; this is checked with a `debug_assert`.
+    pub fn map_fold_layers(self, sources: &[L], to: L) -> CellMapIterMut<'m, L, T, MapFold<L>, S> {
+        debug_assert!(
+            !sources
+                .iter()
+                .any(|source| source.to_index() == to.to_index()),
+            "MapFold's `to` layer must not alias any of its `sources` layers"
+        );
+
+        CellMapIterMut {
+            map: self.map,
+            layerer: MapFold {
+                sources: sources.to_vec().into(),
+                to,
+            },
+            slicer: self.slicer,
+        }
+    }
+
+    /// Alias for [`CellMapIterMut::map_fold_layers`], which already does exactly this: fold an
+    /// arbitrary set of `sources` layers into a single `to` layer, one cell at a time, using the
+    /// same `as_ptr`-per-source/`as_mut_ptr`-for-`to` unsafe discipline with the same
+    /// not-aliasing-`to`-debug_assert. Kept as a separate name since "combine" is how some callers
+    /// think of this operation (e.g. fusing height/gradient/roughness into a traversability cost).
+    pub fn combine_layers(self, sources: &[L], to: L) -> CellMapIterMut<'m, L, T, MapFold<L>, S> {
+        self.map_fold_layers(sources, to)
+    }
+
+    /// Converts this iterator to use a [`Stacked`] layerer, producing every layer's value for each
+    /// cell before moving to the next cell.
+    pub fn stacked(self) -> CellMapIterMut<'m, L, T, Stacked<L>, S> {
+        CellMapIterMut {
+            map: self.map,
+            layerer: Stacked { layers: L::all() },
+            slicer: self.slicer,
+        }
+    }
+
+    /// Converts this iterator to use a [`Stacked`] layerer restricted to the given `layers`,
+    /// producing each selected layer's value for a cell before moving to the next cell.
+    ///
+    /// `layers` must not contain duplicates, since each would otherwise hand out two simultaneous
+    /// mutable references into the same layer; this is checked with a `debug_assert`.
+    pub fn stacked_layers(self, layers: &[L]) -> CellMapIterMut<'m, L, T, Stacked<L>, S> {
+        debug_assert!(
+            {
+                let mut indices: Vec<_> = layers.iter().map(Layer::to_index).collect();
+                let len = indices.len();
+                indices.sort_unstable();
+                indices.dedup();
+                indices.len() == len
+            },
+            "Stacked's `layers` must not contain duplicates"
+        );
+
+        CellMapIterMut {
+            map: self.map,
+            layerer: Stacked {
+                layers: layers.to_vec(),
+            },
+            slicer: self.slicer,
+        }
+    }
+
     /// Converts this iterator to also produce the index of the iterated item as well as its value.
     pub fn indexed(self) -> CellMapIterMut<'m, L, T, R, Indexed<'m, L, T, S>> {
         let current_layer = self.layerer.current().unwrap();
@@ -344,6 +724,19 @@ where
     type Item = S::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // Skip any front layer this particular map has no storage for, e.g. a `DynamicLayer`
+        // name interned by a different `CellMap<DynamicLayer, _>` that this map never called
+        // `add_layer` for. `Layer::all()` is a process-wide view for `DynamicLayer`, not a
+        // per-map one, so this can't be ruled out before iteration starts.
+        while let Some(layer) = self.layerer.layers.front() {
+            if self.map.has_layer(layer) {
+                break;
+            }
+
+            self.layerer.layers.pop_front();
+            self.slicer.reset(self.layerer.current());
+        }
+
         let item = self
             .slicer
             .slice(&self.map.data[self.layerer.layers.front()?.to_index()]);
@@ -367,6 +760,19 @@ where
     type Item = S::OutputMut;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // Skip any front layer this particular map has no storage for, e.g. a `DynamicLayer`
+        // name interned by a different `CellMap<DynamicLayer, _>` that this map never called
+        // `add_layer` for. `Layer::all()` is a process-wide view for `DynamicLayer`, not a
+        // per-map one, so this can't be ruled out before iteration starts.
+        while let Some(layer) = self.layerer.layers.front() {
+            if self.map.has_layer(layer) {
+                break;
+            }
+
+            self.layerer.layers.pop_front();
+            self.slicer.reset(self.layerer.current());
+        }
+
         // Note: use of unsafe
         //
         // We must guarantee that we don't hand out multiple mutable references to the data stored
@@ -392,6 +798,202 @@ where
     }
 }
 
+impl<'m, L, T, S> ExactSizeIterator for CellMapIter<'m, L, T, Single<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn len(&self) -> usize {
+        self.slicer.remaining()
+    }
+}
+
+impl<'m, L, T, S> ExactSizeIterator for CellMapIterMut<'m, L, T, Single<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn len(&self) -> usize {
+        self.slicer.remaining()
+    }
+}
+
+impl<'m, L, T, S> DoubleEndedIterator for CellMapIter<'m, L, T, Single<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self
+            .slicer
+            .slice_back(&self.map.data[self.layerer.layer.to_index()]);
+
+        self.slicer.advance_back();
+
+        item
+    }
+}
+
+impl<'m, L, T, S> DoubleEndedIterator for CellMapIterMut<'m, L, T, Single<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // Note: use of unsafe
+        //
+        // We must guarantee that we don't hand out multiple mutable references to the data stored
+        // in the map, which we can do since each call to this function will drop the previously
+        // returned reference first.
+        let item = unsafe {
+            let layer_ptr = self
+                .map
+                .data
+                .as_mut_ptr()
+                .add(self.layerer.layer.to_index());
+            self.slicer.slice_mut_back(&mut *layer_ptr)
+        };
+
+        self.slicer.advance_back();
+
+        item
+    }
+}
+
+/// `Many<L>` only gets [`ExactSizeIterator`], not [`DoubleEndedIterator`].
+///
+/// Computing `len()` just needs the remaining layers' full sizes plus however much of the current
+/// front layer is left, which doesn't require touching `next()`. A correct `next_back()` would
+/// need a second, independent cursor that pops layers off the back of `layerer.layers` as it
+/// exhausts them, while the front cursor keeps popping off the front of the *same* deque — once
+/// those two cursors converge on the one layer left between them, they'd have to coordinate to
+/// avoid re-yielding or skipping cells, and `Iterator`'s existing blanket impl for
+/// `CellMapIter<Many<L>, S>` can't be specialised per-`S` to add that coordination without
+/// conflicting with this impl. Left unimplemented rather than shipped unsound.
+impl<'m, L, T, S> ExactSizeIterator for CellMapIter<'m, L, T, Many<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn len(&self) -> usize {
+        match self.layerer.layers.len() {
+            0 => 0,
+            n => self.slicer.remaining() + self.slicer.total() * (n - 1),
+        }
+    }
+}
+
+/// See the [`ExactSizeIterator`] impl for [`CellMapIter`] with a [`Many`] layerer for why there's
+/// no [`DoubleEndedIterator`] impl here.
+impl<'m, L, T, S> ExactSizeIterator for CellMapIterMut<'m, L, T, Many<L>, S>
+where
+    L: Layer,
+    S: ExactSlicer<'m, L, T>,
+{
+    fn len(&self) -> usize {
+        match self.layerer.layers.len() {
+            0 => 0,
+            n => self.slicer.remaining() + self.slicer.total() * (n - 1),
+        }
+    }
+}
+
+impl<'m, L, T, S> Iterator for CellMapIter<'m, L, T, Stacked<L>, S>
+where
+    L: Layer,
+    S: Slicer<'m, L, T>,
+{
+    type Item = (Point2<usize>, Vec<S::Output>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.slicer.index()?;
+
+        let values = self
+            .layerer
+            .layers
+            .iter()
+            .map(|layer| self.slicer.slice(&self.map.data[layer.to_index()]))
+            .collect::<Option<Vec<_>>>()?;
+
+        self.slicer.advance();
+
+        Some((index, values))
+    }
+}
+
+impl<'m, L, T> CellMapIter<'m, L, T, Stacked<L>, Cells>
+where
+    L: Layer,
+    T: 'm,
+{
+    /// Folds the selected layers at each cell into a single value, producing a new [`CellMap`]
+    /// with a single [`OneLayer`] layer holding the results.
+    ///
+    /// This is the per-cell, cross-layer counterpart to [`CellMap::merge`](crate::CellMap::merge):
+    /// `merge` combines two maps cell-by-cell, whereas `reduce_layers` collapses the *stack of
+    /// layers at one cell* of a single map into a derived value (e.g. collapsing
+    /// semantic-probability layers into an argmax class layer, or summing hazard layers into one
+    /// cost layer). `f` is called once per selected layer at each cell as
+    /// `f(acc, layer, value)`, starting from `init`, so the reducer can branch on which layer it's
+    /// currently folding.
+    pub fn reduce_layers<U, F>(self, init: U, mut f: F) -> CellMap<OneLayer, U>
+    where
+        U: Clone,
+        F: FnMut(U, &L, &T) -> U,
+    {
+        let params = self.map.params();
+        let layers = self.layerer.layers.clone();
+        let shape = params.cell_bounds.get_shape();
+
+        let mut result = Array2::from_elem(shape, init.clone());
+
+        for (index, values) in self {
+            let mut acc = init.clone();
+
+            for (layer, value) in layers.iter().zip(values) {
+                acc = f(acc, layer, value);
+            }
+
+            result[index.as_array2_index()] = acc;
+        }
+
+        CellMap::new_from_data(params, vec![result])
+            .expect("result always matches params.cell_bounds and OneLayer::NUM_LAYERS")
+    }
+}
+
+impl<'m, L, T, S> Iterator for CellMapIterMut<'m, L, T, Stacked<L>, S>
+where
+    L: Layer,
+    S: Slicer<'m, L, T>,
+{
+    type Item = (Point2<usize>, Vec<S::OutputMut>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.slicer.index()?;
+
+        // Note: use of unsafe
+        //
+        // We must guarantee that we don't hand out multiple mutable references to the data stored
+        // in the map, which holds here since each layer in `self.layerer.layers` is only ever
+        // sliced once per cell, each from a distinct index into `self.map.data`.
+        let values = unsafe {
+            self.layerer
+                .layers
+                .iter()
+                .map(|layer| {
+                    let layer_ptr = self.map.data.as_mut_ptr().add(layer.to_index());
+                    self.slicer.slice_mut(&mut *layer_ptr)
+                })
+                .collect::<Option<Vec<_>>>()
+        }?;
+
+        self.slicer.advance();
+
+        Some((index, values))
+    }
+}
+
 impl<'m, L, T, S> Iterator for CellMapIterMut<'m, L, T, Map<L>, S>
 where
     L: Layer,
@@ -422,3 +1024,39 @@ where
         }
     }
 }
+
+impl<'m, L, T, S> Iterator for CellMapIterMut<'m, L, T, MapFold<L>, S>
+where
+    L: Layer,
+    S: Slicer<'m, L, T>,
+{
+    type Item = (Vec<S::Output>, S::OutputMut);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Note: use of unsafe
+        //
+        // We must guarantee that we don't hand out multiple mutable references to the data stored
+        // in the map. This holds here since `to` is guaranteed (via `map_fold_layers`'s
+        // debug_assert) not to alias any layer in `sources`, so the immutable borrows below and the
+        // single mutable borrow of `to` never touch the same layer.
+        let sources = unsafe {
+            self.layerer
+                .sources
+                .iter()
+                .map(|source| {
+                    let layer_ptr = self.map.data.as_ptr().add(source.to_index());
+                    self.slicer.slice(&*layer_ptr)
+                })
+                .collect::<Option<Vec<_>>>()
+        }?;
+
+        let to = unsafe {
+            let layer_ptr = self.map.data.as_mut_ptr().add(self.layerer.to.to_index());
+            self.slicer.slice_mut(&mut *layer_ptr)
+        }?;
+
+        self.slicer.advance();
+
+        Some((sources, to))
+    }
+}
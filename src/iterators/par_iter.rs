@@ -0,0 +1,1839 @@
+//! Parallel iteration over [`CellMap`]s, powered by `rayon`.
+//!
+//! This module (and the [`CellMap::par_iter()`]/[`CellMap::par_iter_mut()`] constructors) are
+//! only available when the `rayon` feature is enabled, so that consumers who don't need
+//! parallelism aren't forced to pull in the dependency.
+//!
+//! Unlike the [`Slicer`]-based iterators in [`crate::iterators`], [`CellMapParIter`],
+//! [`CellMapParIterMut`] and [`CellMapParWindowIter`] are index-based: the total number of cells
+//! (`L::NUM_LAYERS * num_cells.x * num_cells.y`, or the equivalent count of window positions for
+//! [`CellMapParWindowIter`]) is known up front, so a flat global index can be split arbitrarily
+//! between worker threads and decoded back into `(layer, x, y)` on demand. This is what makes the
+//! mutable iterator splittable, since it doesn't need to carry any mutable iteration state between
+//! calls to `next()`.
+//!
+//! [`CellMapParWindowIter`] has no mutable counterpart, unlike [`CellMapParIterMut`]: windows
+//! centred on neighbouring cells overlap, so handing them out to different threads at the same
+//! time would let two threads write to the same cell, which `CellMapParIterMut`'s disjoint,
+//! single-cell splitting can't.
+//!
+//! [`CellMapParLayerIter`] and [`CellMapParLayerIterMut`] are the per-layer analogues, restricting
+//! the same flat-index/`Producer` scheme to a single layer's [`ndarray::Array2`] so callers who
+//! only care about one layer (e.g. running a cost function over just an occupancy layer) don't
+//! pay for decoding a layer index on every cell.
+//!
+//! [`CellMapParIter`] and [`CellMapParIterMut`] support the same `.layer()`/`.layers()`/
+//! `.indexed()` combinators as the serial iterators: `.layer()` hands back the cheaper
+//! [`CellMapParLayerIter`]/[`CellMapParLayerIterMut`] (no layer index left to decode once there's
+//! only one layer), `.layers()` hands back [`CellMapParLayersIter`]/[`CellMapParLayersIterMut`]
+//! (the same flat-index scheme decoded against the chosen subset instead of [`Layer::all()`]), and
+//! `.indexed()` is built from `.enumerate()` plus the same decode functions the rest of this module
+//! uses. `CellMapParWindowIter` doesn't support these yet.
+//!
+//! [`Layer::all()`]: crate::Layer::all
+//!
+//! [`CellMap::par_window_map()`] takes a different shape to the rest of this module: rather than
+//! an `Iterator`-like object, it's a one-shot call that reads an overlapping window around every
+//! cell (which is safe in parallel, since reads don't alias) and writes the result of a user
+//! closure into the corresponding cell of a freshly built output map (which is also safe, since
+//! every task writes a distinct cell). This sidesteps the read-vs-write aliasing hazard that rules
+//! out an in-place, overlapping-window `par_window_iter_mut`.
+//!
+//! [`CellMap`]: crate::CellMap
+//! [`CellMap::iter()`]: crate::CellMap::iter
+//! [`CellMap::par_iter()`]: crate::CellMap::par_iter
+//! [`CellMap::par_iter_mut()`]: crate::CellMap::par_iter_mut
+//! [`CellMap::par_window_map()`]: crate::CellMap::par_window_map
+//! [`Slicer`]: crate::iterators::slicers::Slicer
+
+// ------------------------------------------------------------------------------------------------
+// IMPORTS
+// ------------------------------------------------------------------------------------------------
+
+use std::marker::PhantomData;
+
+use nalgebra::{Point2, Vector2};
+use ndarray::{s, Array2, ArrayView2};
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+use crate::{CellMap, Error, Layer};
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A `rayon` [`IndexedParallelIterator`] over every cell in every layer of a [`CellMap`], in the
+/// same layer-y-x order as [`CellMap::iter()`].
+///
+/// Construct one with [`CellMap::par_iter()`].
+///
+/// [`CellMap::iter()`]: crate::CellMap::iter
+/// [`CellMap::par_iter()`]: crate::CellMap::par_iter
+#[derive(Debug)]
+pub struct CellMapParIter<'m, L, T>
+where
+    L: Layer,
+{
+    pub(crate) map: &'m CellMap<L, T>,
+}
+
+/// A `rayon` [`IndexedParallelIterator`] providing mutable access to every cell in every layer of
+/// a [`CellMap`], in the same layer-y-x order as [`CellMap::iter_mut()`].
+///
+/// Construct one with [`CellMap::par_iter_mut()`].
+///
+/// [`CellMap::iter_mut()`]: crate::CellMap::iter_mut
+/// [`CellMap::par_iter_mut()`]: crate::CellMap::par_iter_mut
+#[derive(Debug)]
+pub struct CellMapParIterMut<'m, L, T>
+where
+    L: Layer,
+{
+    pub(crate) map: &'m mut CellMap<L, T>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// IMPLS
+// ------------------------------------------------------------------------------------------------
+
+impl<'m, L, T> CellMapParIter<'m, L, T>
+where
+    L: Layer,
+{
+    /// Restricts this iterator to a single layer, mirroring [`CellMapIter::layer()`].
+    ///
+    /// This produces a [`CellMapParLayerIter`] rather than `Self`, since once only one layer is
+    /// left there's no layer index left to decode per cell.
+    ///
+    /// [`CellMapIter::layer()`]: crate::iterators::CellMapIter::layer
+    pub fn layer(self, layer: L) -> CellMapParLayerIter<'m, T> {
+        CellMapParLayerIter {
+            data: &self.map.data[layer.to_index()],
+        }
+    }
+
+    /// Restricts this iterator to the given `layers`, in the order given, mirroring
+    /// [`CellMapIter::layers()`].
+    ///
+    /// [`CellMapIter::layers()`]: crate::iterators::CellMapIter::layers
+    pub fn layers(self, layers: &[L]) -> CellMapParLayersIter<'m, L, T> {
+        CellMapParLayersIter {
+            map: self.map,
+            layers: layers.to_vec(),
+        }
+    }
+}
+
+impl<'m, L, T> CellMapParIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    /// Converts this iterator to also yield the `(layer, cell index)` of each item, mirroring
+    /// [`CellMapIter::indexed()`].
+    ///
+    /// [`CellMapIter::indexed()`]: crate::iterators::CellMapIter::indexed
+    pub fn indexed(self) -> impl IndexedParallelIterator<Item = ((L, Point2<usize>), &'m T)> {
+        let num_cells = self.map.num_cells();
+        self.enumerate()
+            .map(move |(i, value)| (decode::<L>(i, num_cells), value))
+    }
+}
+
+impl<'m, L, T> CellMapParIterMut<'m, L, T>
+where
+    L: Layer,
+{
+    /// Restricts this iterator to a single layer, mirroring [`CellMapIterMut::layer()`].
+    ///
+    /// This produces a [`CellMapParLayerIterMut`] rather than `Self`, since once only one layer is
+    /// left there's no layer index left to decode per cell.
+    ///
+    /// [`CellMapIterMut::layer()`]: crate::iterators::CellMapIterMut::layer
+    pub fn layer(self, layer: L) -> CellMapParLayerIterMut<'m, T> {
+        CellMapParLayerIterMut {
+            data: &mut self.map.data[layer.to_index()],
+        }
+    }
+
+    /// Restricts this iterator to the given `layers`, in the order given, mirroring
+    /// [`CellMapIterMut::layers()`].
+    ///
+    /// [`CellMapIterMut::layers()`]: crate::iterators::CellMapIterMut::layers
+    pub fn layers(self, layers: &[L]) -> CellMapParLayersIterMut<'m, L, T> {
+        CellMapParLayersIterMut {
+            map: self.map,
+            layers: layers.to_vec(),
+        }
+    }
+}
+
+impl<'m, L, T> CellMapParIterMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    /// Converts this iterator to also yield the `(layer, cell index)` of each item, mirroring
+    /// [`CellMapIterMut::indexed()`].
+    ///
+    /// [`CellMapIterMut::indexed()`]: crate::iterators::CellMapIterMut::indexed
+    pub fn indexed(self) -> impl IndexedParallelIterator<Item = ((L, Point2<usize>), &'m mut T)> {
+        let num_cells = self.map.num_cells();
+        self.enumerate()
+            .map(move |(i, value)| (decode::<L>(i, num_cells), value))
+    }
+}
+
+/// Decodes a flat global cell index, in layer-y-x order, into the layer and cell index it refers
+/// to.
+fn decode<L: Layer>(global: usize, num_cells: Vector2<usize>) -> (L, Point2<usize>) {
+    let per_layer = num_cells.x * num_cells.y;
+    let layer = L::from_index(global / per_layer);
+    let in_layer = global % per_layer;
+    let y = in_layer / num_cells.x;
+    let x = in_layer % num_cells.x;
+    (layer, Point2::new(x, y))
+}
+
+/// Decodes a flat global window index, in layer-y-x order over the interior cells a
+/// `semi_width`-sized window fits around, into the layer and the centre cell of the
+/// `(2 * semi_width + 1)`-sized window it refers to.
+fn decode_window<L: Layer>(
+    global: usize,
+    interior: Vector2<usize>,
+    semi_width: Vector2<usize>,
+) -> (L, Point2<usize>) {
+    let per_layer = interior.x * interior.y;
+    let layer = L::from_index(global / per_layer);
+    let in_layer = global % per_layer;
+    let y = in_layer / interior.x + semi_width.y;
+    let x = in_layer % interior.x + semi_width.x;
+    (layer, Point2::new(x, y))
+}
+
+/// A `rayon` [`IndexedParallelIterator`] over windows of cells in every layer of a [`CellMap`], in
+/// the same layer-y-x order as [`CellMap::window_iter()`].
+///
+/// Construct one with [`CellMap::par_window_iter()`].
+///
+/// [`CellMap::window_iter()`]: crate::CellMap::window_iter
+/// [`CellMap::par_window_iter()`]: crate::CellMap::par_window_iter
+#[derive(Debug)]
+pub struct CellMapParWindowIter<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    semi_width: Vector2<usize>,
+    interior: Vector2<usize>,
+}
+
+impl<'m, L, T> CellMapParWindowIter<'m, L, T>
+where
+    L: Layer,
+{
+    pub(crate) fn new(map: &'m CellMap<L, T>, semi_width: Vector2<usize>) -> Result<Self, Error> {
+        // As for `Windows`, the views are contiguous slices of physical storage, which can't
+        // follow a scrolled map's ring buffer wrap.
+        if map.metadata.start_index != Vector2::zeros() {
+            return Err(Error::WindowedIterOnScrolledMap(map.metadata.start_index));
+        }
+
+        let num_cells = map.num_cells();
+        let window = semi_width * 2 + Vector2::new(1, 1);
+
+        if window.x > num_cells.x || window.y > num_cells.y {
+            return Err(Error::WindowLargerThanMap(window, num_cells));
+        }
+
+        let interior = Vector2::new(num_cells.x - window.x + 1, num_cells.y - window.y + 1);
+
+        Ok(Self {
+            map,
+            semi_width,
+            interior,
+        })
+    }
+}
+
+impl<'m, L, T> ParallelIterator for CellMapParIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, L, T> IndexedParallelIterator for CellMapParIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    fn len(&self) -> usize {
+        let num_cells = self.map.num_cells();
+        L::NUM_LAYERS * num_cells.x * num_cells.y
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let len = self.len();
+        callback.callback(CellMapProducer {
+            map: self.map,
+            start: 0,
+            end: len,
+        })
+    }
+}
+
+struct CellMapProducer<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    start: usize,
+    end: usize,
+}
+
+impl<'m, L, T> Producer for CellMapProducer<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+    type IntoIter = CellMapProducerIter<'m, L, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapProducerIter {
+            map: self.map,
+            index: self.start,
+            end: self.end,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapProducer {
+                map: self.map,
+                start: self.start,
+                end: mid,
+            },
+            CellMapProducer {
+                map: self.map,
+                start: mid,
+                end: self.end,
+            },
+        )
+    }
+}
+
+struct CellMapProducerIter<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    index: usize,
+    end: usize,
+}
+
+impl<'m, L, T> Iterator for CellMapProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    type Item = &'m T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let (layer, index) = decode::<L>(self.index, self.map.num_cells());
+        self.index += 1;
+
+        Some(&self.map[(layer, index)])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, L, T> DoubleEndedIterator for CellMapProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let (layer, index) = decode::<L>(self.end, self.map.num_cells());
+
+        Some(&self.map[(layer, index)])
+    }
+}
+
+impl<'m, L, T> ExactSizeIterator for CellMapProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+}
+
+impl<'m, L, T> ParallelIterator for CellMapParWindowIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = ArrayView2<'m, T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, L, T> IndexedParallelIterator for CellMapParWindowIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    fn len(&self) -> usize {
+        L::NUM_LAYERS * self.interior.x * self.interior.y
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let len = self.len();
+        callback.callback(CellMapWindowProducer {
+            map: self.map,
+            start: 0,
+            end: len,
+            semi_width: self.semi_width,
+            interior: self.interior,
+        })
+    }
+}
+
+struct CellMapWindowProducer<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    start: usize,
+    end: usize,
+    semi_width: Vector2<usize>,
+    interior: Vector2<usize>,
+}
+
+impl<'m, L, T> Producer for CellMapWindowProducer<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = ArrayView2<'m, T>;
+    type IntoIter = CellMapWindowProducerIter<'m, L, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapWindowProducerIter {
+            map: self.map,
+            index: self.start,
+            end: self.end,
+            semi_width: self.semi_width,
+            interior: self.interior,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapWindowProducer {
+                map: self.map,
+                start: self.start,
+                end: mid,
+                semi_width: self.semi_width,
+                interior: self.interior,
+            },
+            CellMapWindowProducer {
+                map: self.map,
+                start: mid,
+                end: self.end,
+                semi_width: self.semi_width,
+                interior: self.interior,
+            },
+        )
+    }
+}
+
+struct CellMapWindowProducerIter<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    index: usize,
+    end: usize,
+    semi_width: Vector2<usize>,
+    interior: Vector2<usize>,
+}
+
+impl<'m, L, T> CellMapWindowProducerIter<'m, L, T>
+where
+    L: Layer,
+{
+    /// Slices out the window centred on `centre`, from the given layer.
+    fn window_view(&self, layer: &L, centre: Point2<usize>) -> ArrayView2<'m, T> {
+        let shape = (self.semi_width.y * 2 + 1, self.semi_width.x * 2 + 1);
+        self.map.data[layer.to_index()].slice(s![
+            centre.y - self.semi_width.y..centre.y - self.semi_width.y + shape.0,
+            centre.x - self.semi_width.x..centre.x - self.semi_width.x + shape.1
+        ])
+    }
+}
+
+impl<'m, L, T> Iterator for CellMapWindowProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    type Item = ArrayView2<'m, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let (layer, corner) = decode_window::<L>(self.index, self.interior, self.semi_width);
+        self.index += 1;
+
+        Some(self.window_view(&layer, corner))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, L, T> DoubleEndedIterator for CellMapWindowProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let (layer, corner) = decode_window::<L>(self.end, self.interior, self.semi_width);
+
+        Some(self.window_view(&layer, corner))
+    }
+}
+
+impl<'m, L, T> ExactSizeIterator for CellMapWindowProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+}
+
+impl<'m, L, T> ParallelIterator for CellMapParIterMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, L, T> IndexedParallelIterator for CellMapParIterMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    fn len(&self) -> usize {
+        let num_cells = self.map.num_cells();
+        L::NUM_LAYERS * num_cells.x * num_cells.y
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let len = self.len();
+        let num_cells = self.map.num_cells();
+        callback.callback(CellMapProducerMut {
+            map: self.map as *mut CellMap<L, T>,
+            start: 0,
+            end: len,
+            num_cells,
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct CellMapProducerMut<'m, L, T>
+where
+    L: Layer,
+{
+    map: *mut CellMap<L, T>,
+    start: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut CellMap<L, T>>,
+}
+
+// Note: use of unsafe
+//
+// `CellMapProducerMut` is only ever split into disjoint, non-overlapping index ranges (see
+// `split_at` below), so no two instances derived from the same producer can ever hand out
+// references to the same cell at the same time. This mirrors the reasoning used by
+// `CellMapIterMut` in `crate::iterators`.
+unsafe impl<'m, L, T> Send for CellMapProducerMut<'m, L, T>
+where
+    L: Layer + Send,
+    T: Send,
+{
+}
+
+impl<'m, L, T> Producer for CellMapProducerMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+    type IntoIter = CellMapProducerIterMut<'m, L, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapProducerIterMut {
+            map: self.map,
+            index: self.start,
+            end: self.end,
+            num_cells: self.num_cells,
+            _marker: PhantomData,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapProducerMut {
+                map: self.map,
+                start: self.start,
+                end: mid,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+            CellMapProducerMut {
+                map: self.map,
+                start: mid,
+                end: self.end,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+struct CellMapProducerIterMut<'m, L, T>
+where
+    L: Layer,
+{
+    map: *mut CellMap<L, T>,
+    index: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut CellMap<L, T>>,
+}
+
+impl<'m, L, T> Iterator for CellMapProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    type Item = &'m mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let (layer, index) = decode::<L>(self.index, self.num_cells);
+        self.index += 1;
+
+        // SAFETY: see the `Send` impl on `CellMapProducerMut` above; each global index maps to a
+        // unique cell, and this producer only ever sees a disjoint sub-range of indices.
+        unsafe {
+            let phys = (*self.map).metadata.wrap_index(index);
+            let layer_ptr = (*self.map).data.as_mut_ptr().add(layer.to_index());
+            Some((&mut *layer_ptr).get_mut((phys.y, phys.x)).unwrap())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, L, T> DoubleEndedIterator for CellMapProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let (layer, index) = decode::<L>(self.end, self.num_cells);
+
+        // SAFETY: see the `Send` impl on `CellMapProducerMut` above.
+        unsafe {
+            let phys = (*self.map).metadata.wrap_index(index);
+            let layer_ptr = (*self.map).data.as_mut_ptr().add(layer.to_index());
+            Some((&mut *layer_ptr).get_mut((phys.y, phys.x)).unwrap())
+        }
+    }
+}
+
+impl<'m, L, T> ExactSizeIterator for CellMapProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+}
+
+/// Decodes a flat per-layer cell index, in y-x order, into the cell index it refers to.
+fn decode_cell(global: usize, num_cells: Vector2<usize>) -> Point2<usize> {
+    let y = global / num_cells.x;
+    let x = global % num_cells.x;
+    Point2::new(x, y)
+}
+
+/// A `rayon` [`IndexedParallelIterator`] over every cell in a single layer of a [`CellMap`], in
+/// the same y-x order as [`CellMap::iter()`] restricted to that layer.
+///
+/// Construct one with [`CellMap::par_layer_iter()`].
+///
+/// [`CellMap::iter()`]: crate::CellMap::iter
+/// [`CellMap::par_layer_iter()`]: crate::CellMap::par_layer_iter
+#[derive(Debug)]
+pub struct CellMapParLayerIter<'m, T> {
+    pub(crate) data: &'m Array2<T>,
+}
+
+impl<'m, T> ParallelIterator for CellMapParLayerIter<'m, T>
+where
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, T> IndexedParallelIterator for CellMapParLayerIter<'m, T>
+where
+    T: Sync + 'm,
+{
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let num_cells = Vector2::new(self.data.ncols(), self.data.nrows());
+        let len = self.data.len();
+        callback.callback(CellMapLayerProducer {
+            data: self.data,
+            start: 0,
+            end: len,
+            num_cells,
+        })
+    }
+}
+
+struct CellMapLayerProducer<'m, T> {
+    data: &'m Array2<T>,
+    start: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+}
+
+impl<'m, T> Producer for CellMapLayerProducer<'m, T>
+where
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+    type IntoIter = CellMapLayerProducerIter<'m, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapLayerProducerIter {
+            data: self.data,
+            index: self.start,
+            end: self.end,
+            num_cells: self.num_cells,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapLayerProducer {
+                data: self.data,
+                start: self.start,
+                end: mid,
+                num_cells: self.num_cells,
+            },
+            CellMapLayerProducer {
+                data: self.data,
+                start: mid,
+                end: self.end,
+                num_cells: self.num_cells,
+            },
+        )
+    }
+}
+
+struct CellMapLayerProducerIter<'m, T> {
+    data: &'m Array2<T>,
+    index: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+}
+
+impl<'m, T> Iterator for CellMapLayerProducerIter<'m, T>
+where
+    T: 'm,
+{
+    type Item = &'m T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let p = decode_cell(self.index, self.num_cells);
+        self.index += 1;
+
+        Some(&self.data[(p.y, p.x)])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, T> DoubleEndedIterator for CellMapLayerProducerIter<'m, T>
+where
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let p = decode_cell(self.end, self.num_cells);
+
+        Some(&self.data[(p.y, p.x)])
+    }
+}
+
+impl<'m, T> ExactSizeIterator for CellMapLayerProducerIter<'m, T> where T: 'm {}
+
+/// A `rayon` [`IndexedParallelIterator`] providing mutable access to every cell in a single layer
+/// of a [`CellMap`], in the same y-x order as [`CellMap::iter_mut()`] restricted to that layer.
+///
+/// Construct one with [`CellMap::par_layer_iter_mut()`].
+///
+/// [`CellMap::iter_mut()`]: crate::CellMap::iter_mut
+/// [`CellMap::par_layer_iter_mut()`]: crate::CellMap::par_layer_iter_mut
+#[derive(Debug)]
+pub struct CellMapParLayerIterMut<'m, T> {
+    pub(crate) data: &'m mut Array2<T>,
+}
+
+impl<'m, T> ParallelIterator for CellMapParLayerIterMut<'m, T>
+where
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, T> IndexedParallelIterator for CellMapParLayerIterMut<'m, T>
+where
+    T: Send + 'm,
+{
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let num_cells = Vector2::new(self.data.ncols(), self.data.nrows());
+        let len = self.data.len();
+        callback.callback(CellMapLayerProducerMut {
+            data: self.data as *mut Array2<T>,
+            start: 0,
+            end: len,
+            num_cells,
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct CellMapLayerProducerMut<'m, T> {
+    data: *mut Array2<T>,
+    start: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut Array2<T>>,
+}
+
+// Note: use of unsafe
+//
+// `CellMapLayerProducerMut` is only ever split into disjoint, non-overlapping index ranges (see
+// `split_at` below), so no two instances derived from the same producer can ever hand out
+// references to the same cell at the same time. This mirrors the reasoning used by
+// `CellMapProducerMut` above.
+unsafe impl<'m, T> Send for CellMapLayerProducerMut<'m, T> where T: Send {}
+
+impl<'m, T> Producer for CellMapLayerProducerMut<'m, T>
+where
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+    type IntoIter = CellMapLayerProducerIterMut<'m, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapLayerProducerIterMut {
+            data: self.data,
+            index: self.start,
+            end: self.end,
+            num_cells: self.num_cells,
+            _marker: PhantomData,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapLayerProducerMut {
+                data: self.data,
+                start: self.start,
+                end: mid,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+            CellMapLayerProducerMut {
+                data: self.data,
+                start: mid,
+                end: self.end,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+struct CellMapLayerProducerIterMut<'m, T> {
+    data: *mut Array2<T>,
+    index: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut Array2<T>>,
+}
+
+impl<'m, T> Iterator for CellMapLayerProducerIterMut<'m, T>
+where
+    T: 'm,
+{
+    type Item = &'m mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let p = decode_cell(self.index, self.num_cells);
+        self.index += 1;
+
+        // SAFETY: see the `Send` impl on `CellMapLayerProducerMut` above; each global index maps
+        // to a unique cell, and this producer only ever sees a disjoint sub-range of indices.
+        unsafe { Some((&mut *self.data).get_mut((p.y, p.x)).unwrap()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, T> DoubleEndedIterator for CellMapLayerProducerIterMut<'m, T>
+where
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let p = decode_cell(self.end, self.num_cells);
+
+        // SAFETY: see the `Send` impl on `CellMapLayerProducerMut` above.
+        unsafe { Some((&mut *self.data).get_mut((p.y, p.x)).unwrap()) }
+    }
+}
+
+impl<'m, T> ExactSizeIterator for CellMapLayerProducerIterMut<'m, T> where T: 'm {}
+
+/// Decodes a flat global cell index, in layer-y-x order over a chosen subset of `layers`, into the
+/// layer and cell index it refers to.
+fn decode_layers<L: Layer>(
+    global: usize,
+    layers: &[L],
+    num_cells: Vector2<usize>,
+) -> (L, Point2<usize>) {
+    let per_layer = num_cells.x * num_cells.y;
+    let layer = layers[global / per_layer].clone();
+    let in_layer = global % per_layer;
+    let y = in_layer / num_cells.x;
+    let x = in_layer % num_cells.x;
+    (layer, Point2::new(x, y))
+}
+
+/// A `rayon` [`IndexedParallelIterator`] over every cell in a chosen subset of layers of a
+/// [`CellMap`], in the same layer-y-x order as [`CellMap::iter()`] restricted to those layers.
+///
+/// Construct one with [`CellMapParIter::layers()`].
+#[derive(Debug)]
+pub struct CellMapParLayersIter<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    layers: Vec<L>,
+}
+
+impl<'m, L, T> ParallelIterator for CellMapParLayersIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, L, T> IndexedParallelIterator for CellMapParLayersIter<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    fn len(&self) -> usize {
+        let num_cells = self.map.num_cells();
+        self.layers.len() * num_cells.x * num_cells.y
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let len = self.len();
+        callback.callback(CellMapLayersProducer {
+            map: self.map,
+            layers: self.layers,
+            start: 0,
+            end: len,
+        })
+    }
+}
+
+struct CellMapLayersProducer<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    layers: Vec<L>,
+    start: usize,
+    end: usize,
+}
+
+impl<'m, L, T> Producer for CellMapLayersProducer<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Sync + 'm,
+{
+    type Item = &'m T;
+    type IntoIter = CellMapLayersProducerIter<'m, L, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapLayersProducerIter {
+            map: self.map,
+            layers: self.layers,
+            index: self.start,
+            end: self.end,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapLayersProducer {
+                map: self.map,
+                layers: self.layers.clone(),
+                start: self.start,
+                end: mid,
+            },
+            CellMapLayersProducer {
+                map: self.map,
+                layers: self.layers,
+                start: mid,
+                end: self.end,
+            },
+        )
+    }
+}
+
+struct CellMapLayersProducerIter<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m CellMap<L, T>,
+    layers: Vec<L>,
+    index: usize,
+    end: usize,
+}
+
+impl<'m, L, T> Iterator for CellMapLayersProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    type Item = &'m T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let (layer, index) = decode_layers::<L>(self.index, &self.layers, self.map.num_cells());
+        self.index += 1;
+
+        Some(&self.map[(layer, index)])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, L, T> DoubleEndedIterator for CellMapLayersProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let (layer, index) = decode_layers::<L>(self.end, &self.layers, self.map.num_cells());
+
+        Some(&self.map[(layer, index)])
+    }
+}
+
+impl<'m, L, T> ExactSizeIterator for CellMapLayersProducerIter<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+}
+
+/// A `rayon` [`IndexedParallelIterator`] providing mutable access to every cell in a chosen subset
+/// of layers of a [`CellMap`], in the same layer-y-x order as [`CellMap::iter_mut()`] restricted
+/// to those layers.
+///
+/// Construct one with [`CellMapParIterMut::layers()`].
+#[derive(Debug)]
+pub struct CellMapParLayersIterMut<'m, L, T>
+where
+    L: Layer,
+{
+    map: &'m mut CellMap<L, T>,
+    layers: Vec<L>,
+}
+
+impl<'m, L, T> ParallelIterator for CellMapParLayersIterMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'m, L, T> IndexedParallelIterator for CellMapParLayersIterMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    fn len(&self) -> usize {
+        let num_cells = self.map.num_cells();
+        self.layers.len() * num_cells.x * num_cells.y
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        let len = self.len();
+        let num_cells = self.map.num_cells();
+        callback.callback(CellMapLayersProducerMut {
+            map: self.map as *mut CellMap<L, T>,
+            layers: self.layers,
+            start: 0,
+            end: len,
+            num_cells,
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct CellMapLayersProducerMut<'m, L, T>
+where
+    L: Layer,
+{
+    map: *mut CellMap<L, T>,
+    layers: Vec<L>,
+    start: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut CellMap<L, T>>,
+}
+
+// Note: use of unsafe
+//
+// `CellMapLayersProducerMut` is only ever split into disjoint, non-overlapping index ranges (see
+// `split_at` below), so no two instances derived from the same producer can ever hand out
+// references to the same cell at the same time. This mirrors the reasoning used by
+// `CellMapProducerMut` above.
+unsafe impl<'m, L, T> Send for CellMapLayersProducerMut<'m, L, T>
+where
+    L: Layer + Send,
+    T: Send,
+{
+}
+
+impl<'m, L, T> Producer for CellMapLayersProducerMut<'m, L, T>
+where
+    L: Layer + Send + Sync,
+    T: Send + 'm,
+{
+    type Item = &'m mut T;
+    type IntoIter = CellMapLayersProducerIterMut<'m, L, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CellMapLayersProducerIterMut {
+            map: self.map,
+            layers: self.layers,
+            index: self.start,
+            end: self.end,
+            num_cells: self.num_cells,
+            _marker: PhantomData,
+        }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            CellMapLayersProducerMut {
+                map: self.map,
+                layers: self.layers.clone(),
+                start: self.start,
+                end: mid,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+            CellMapLayersProducerMut {
+                map: self.map,
+                layers: self.layers,
+                start: mid,
+                end: self.end,
+                num_cells: self.num_cells,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+struct CellMapLayersProducerIterMut<'m, L, T>
+where
+    L: Layer,
+{
+    map: *mut CellMap<L, T>,
+    layers: Vec<L>,
+    index: usize,
+    end: usize,
+    num_cells: Vector2<usize>,
+    _marker: PhantomData<&'m mut CellMap<L, T>>,
+}
+
+impl<'m, L, T> Iterator for CellMapLayersProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    type Item = &'m mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let (layer, index) = decode_layers::<L>(self.index, &self.layers, self.num_cells);
+        self.index += 1;
+
+        // SAFETY: see the `Send` impl on `CellMapLayersProducerMut` above; each global index maps
+        // to a unique cell, and this producer only ever sees a disjoint sub-range of indices.
+        unsafe {
+            let phys = (*self.map).metadata.wrap_index(index);
+            let layer_ptr = (*self.map).data.as_mut_ptr().add(layer.to_index());
+            Some((&mut *layer_ptr).get_mut((phys.y, phys.x)).unwrap())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'m, L, T> DoubleEndedIterator for CellMapLayersProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        let (layer, index) = decode_layers::<L>(self.end, &self.layers, self.num_cells);
+
+        // SAFETY: see the `Send` impl on `CellMapLayersProducerMut` above.
+        unsafe {
+            let phys = (*self.map).metadata.wrap_index(index);
+            let layer_ptr = (*self.map).data.as_mut_ptr().add(layer.to_index());
+            Some((&mut *layer_ptr).get_mut((phys.y, phys.x)).unwrap())
+        }
+    }
+}
+
+impl<'m, L, T> ExactSizeIterator for CellMapLayersProducerIterMut<'m, L, T>
+where
+    L: Layer,
+    T: 'm,
+{
+}
+
+/// Controls what [`CellMap::par_window_map()`] does with the parts of a window that fall outside
+/// the map.
+///
+/// This plays the same role [`WindowPadding`] plays for [`PaddedWindows`], but isn't restricted to
+/// `T: Default`: [`BorderMode::Constant`] can supply any fill value, which `WindowPadding` can't
+/// express since its `Zero` variant has no generic parameter to hold one.
+///
+/// [`CellMap::par_window_map()`]: crate::CellMap::par_window_map
+/// [`WindowPadding`]: crate::iterators::slicers::WindowPadding
+/// [`PaddedWindows`]: crate::iterators::slicers::PaddedWindows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderMode<T> {
+    /// Don't evaluate `f` for center cells within `radius` of the map edge; those output cells are
+    /// left holding the output map's default value.
+    Skip,
+
+    /// Treat out-of-bounds cells as having the value of the nearest in-bounds cell.
+    Clamp,
+
+    /// Treat out-of-bounds cells as this constant value.
+    Constant(T),
+}
+
+/// Builds the owned window of `(2 * radius + 1)`-sized cells centred on `center` in `data`,
+/// synthesising the out-of-bounds parts according to `border`.
+///
+/// Mirrors [`PaddedWindows`]'s `slice`, which this doesn't reuse directly since it's keyed on
+/// [`BorderMode`] rather than [`WindowPadding`].
+///
+/// [`PaddedWindows`]: crate::iterators::slicers::PaddedWindows
+/// [`WindowPadding`]: crate::iterators::slicers::WindowPadding
+fn sample_window<T: Clone>(
+    data: &Array2<T>,
+    num_cells: Vector2<usize>,
+    start_index: Vector2<usize>,
+    center: Point2<usize>,
+    radius: Vector2<usize>,
+    border: &BorderMode<T>,
+) -> Array2<T> {
+    let shape = (radius.y * 2 + 1, radius.x * 2 + 1);
+    let physical_index = |x: usize, y: usize| {
+        (
+            (x + start_index.x) % num_cells.x,
+            (y + start_index.y) % num_cells.y,
+        )
+    };
+
+    Array2::from_shape_fn(shape, |(wy, wx)| {
+        let sx = center.x as isize + wx as isize - radius.x as isize;
+        let sy = center.y as isize + wy as isize - radius.y as isize;
+
+        if sx >= 0 && sy >= 0 && (sx as usize) < num_cells.x && (sy as usize) < num_cells.y {
+            let (px, py) = physical_index(sx as usize, sy as usize);
+            data[[py, px]].clone()
+        } else {
+            match border {
+                BorderMode::Skip => {
+                    unreachable!("Skip-mode center indices keep every window fully in bounds")
+                }
+                BorderMode::Clamp => {
+                    let cx = sx.clamp(0, num_cells.x as isize - 1) as usize;
+                    let cy = sy.clamp(0, num_cells.y as isize - 1) as usize;
+                    let (px, py) = physical_index(cx, cy);
+                    data[[py, px]].clone()
+                }
+                BorderMode::Constant(v) => v.clone(),
+            }
+        }
+    })
+}
+
+/// A raw pointer wrapper asserting it's sound to send/share across threads.
+///
+/// Used by [`par_window_map`](crate::CellMap::par_window_map) to let worker threads write into
+/// disjoint cells of the same output [`Array2`] buffer; mirrors the reasoning used by
+/// `CellMapProducerMut`'s `Send` impl above, but for a plain pointer instead of a whole producer.
+struct SendPtr<T>(*mut T);
+
+// SAFETY: every use of a `SendPtr` in this module only ever writes to an index it alone was
+// assigned, computed from a disjoint, non-overlapping range of `(y, x)` positions -- see
+// `CellMap::par_window_map()`.
+unsafe impl<T> Send for SendPtr<T> {}
+unsafe impl<T> Sync for SendPtr<T> {}
+
+/// Computes the inclusive-exclusive `(x0, x1, y0, y1)` range of center-cell indices
+/// [`CellMap::par_window_map()`] should evaluate `f` for, given `radius` and `border`.
+///
+/// For [`BorderMode::Clamp`]/[`BorderMode::Constant`] this is every cell in the map; for
+/// [`BorderMode::Skip`] it excludes the `radius` border, same as [`Windows`].
+///
+/// [`CellMap::par_window_map()`]: crate::CellMap::par_window_map
+/// [`Windows`]: crate::iterators::slicers::Windows
+fn center_index_range<T>(
+    num_cells: Vector2<usize>,
+    radius: Vector2<usize>,
+    border: &BorderMode<T>,
+) -> (usize, usize, usize, usize) {
+    match border {
+        BorderMode::Skip => {
+            let x0 = radius.x.min(num_cells.x);
+            let y0 = radius.y.min(num_cells.y);
+            let x1 = num_cells.x.saturating_sub(radius.x).max(x0);
+            let y1 = num_cells.y.saturating_sub(radius.y).max(y0);
+            (x0, x1, y0, y1)
+        }
+        BorderMode::Clamp | BorderMode::Constant(_) => (0, num_cells.x, 0, num_cells.y),
+    }
+}
+
+/// Evaluates `f` over every centred window a single layer's worth of `input`, in parallel, writing
+/// each result into the matching cell of `output`.
+///
+/// `start_index` is `input`'s map's [`CellMapMetadata::start_index`], needed to read `input`
+/// (raw physical storage, potentially ring-buffer-scrolled) at the right place for each logical
+/// center/window cell; `output` is always a freshly-built map with a zero `start_index`, so it's
+/// written at the identity logical-to-physical mapping.
+///
+/// Pulled out of [`CellMap::par_window_map()`] since it's repeated once per layer.
+///
+/// [`CellMap::par_window_map()`]: crate::CellMap::par_window_map
+/// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+pub(crate) fn par_window_map_layer<T, F>(
+    input: &Array2<T>,
+    output: &mut Array2<T>,
+    start_index: Vector2<usize>,
+    radius: Vector2<usize>,
+    border: &BorderMode<T>,
+    f: &F,
+) where
+    T: Clone + Sync,
+    F: Fn(&Array2<T>) -> T + Sync,
+{
+    let num_cells = Vector2::new(input.ncols(), input.nrows());
+    let (x0, x1, y0, y1) = center_index_range(num_cells, radius, border);
+    let out_ptr = SendPtr(output.as_mut_ptr());
+
+    (y0..y1).into_par_iter().for_each(|y| {
+        for x in x0..x1 {
+            let window = sample_window(
+                input,
+                num_cells,
+                start_index,
+                Point2::new(x, y),
+                radius,
+                border,
+            );
+            let value = f(&window);
+
+            // SAFETY: see the `Send`/`Sync` impls on `SendPtr` above; `y` ranges are disjoint
+            // across tasks and `x` is iterated sequentially within one, so each `(y, x)` is
+            // written to exactly once.
+            unsafe {
+                *out_ptr.0.add(y * num_cells.x + x) = value;
+            }
+        }
+    });
+}
+
+// ------------------------------------------------------------------------------------------------
+// TESTS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{cell_map::Bounds, test_utils::TestLayers, CellMap, CellMapParams};
+
+    use super::*;
+
+    /// Builds a map with distinct per-cell values and then shifts it with [`CellMap::move_by`],
+    /// so tests can check that the parallel iterators' view of a cell agrees with
+    /// [`CellMap::get`] once the map's logical and physical indices have diverged.
+    fn shifted_map() -> CellMap<TestLayers, f64> {
+        let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+            CellMapParams {
+                cell_bounds: Bounds::new((0, 6), (0, 6)).unwrap(),
+                cell_size: Vector2::new(1.0, 1.0),
+                ..Default::default()
+            },
+            0.0,
+        );
+
+        for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+            *v = (idx.x * 10 + idx.y) as f64;
+        }
+
+        map.move_by(Vector2::new(2, 0), -1.0);
+
+        map
+    }
+
+    /// Regression test for the ring-buffer wrapping bug in [`CellMapProducerIter`]: before the
+    /// fix, `par_iter` indexed the backing storage directly and so disagreed with
+    /// [`CellMap::get`] once `move_by` had shifted the map's logical origin away from the
+    /// physical origin.
+    #[test]
+    fn par_iter_respects_ring_buffer_after_move_by() {
+        let map = shifted_map();
+
+        map.par_iter()
+            .indexed()
+            .filter(|((layer, _), _)| layer.to_index() == TestLayers::Layer0.to_index())
+            .for_each(|((_, idx), &v)| {
+                assert_eq!(
+                    v,
+                    *map.get(TestLayers::Layer0, idx).unwrap(),
+                    "par_iter returned a different value than CellMap::get at {:?}",
+                    idx
+                );
+            });
+    }
+
+    /// Regression test for the ring-buffer wrapping bug in [`CellMapProducerIterMut`]: before the
+    /// fix, writes through `par_iter_mut` landed on the wrong physical cell once `move_by` had
+    /// shifted the map's logical origin away from the physical origin.
+    #[test]
+    fn par_iter_mut_respects_ring_buffer_after_move_by() {
+        let mut map = shifted_map();
+
+        map.par_iter_mut()
+            .indexed()
+            .filter(|((layer, _), _)| layer.to_index() == TestLayers::Layer0.to_index())
+            .for_each(|((_, idx), v)| {
+                *v = (idx.x * 100 + idx.y) as f64;
+            });
+
+        for idx_x in 0..6 {
+            for idx_y in 0..6 {
+                let idx = Point2::new(idx_x, idx_y);
+                assert_eq!(
+                    *map.get(TestLayers::Layer0, idx).unwrap(),
+                    (idx_x * 100 + idx_y) as f64,
+                    "par_iter_mut wrote to the wrong physical cell for logical index {:?}",
+                    idx
+                );
+            }
+        }
+    }
+
+    /// Regression test for the ring-buffer wrapping bug in [`CellMapLayersProducerIterMut`],
+    /// mirroring [`par_iter_mut_respects_ring_buffer_after_move_by`] but going through the
+    /// `.layers()` combinator instead, which is what [`CellMapLayersProducerIterMut`] backs.
+    #[test]
+    fn par_layers_iter_mut_respects_ring_buffer_after_move_by() {
+        let mut map = shifted_map();
+        let num_cells = map.num_cells();
+
+        map.par_iter_mut()
+            .layers(&[TestLayers::Layer0])
+            .enumerate()
+            .for_each(|(i, v)| {
+                let idx = Point2::new(i % num_cells.x, i / num_cells.x);
+                *v = (idx.x * 100 + idx.y) as f64;
+            });
+
+        for idx_x in 0..6 {
+            for idx_y in 0..6 {
+                let idx = Point2::new(idx_x, idx_y);
+                assert_eq!(
+                    *map.get(TestLayers::Layer0, idx).unwrap(),
+                    (idx_x * 100 + idx_y) as f64,
+                    "par_layers_iter_mut wrote to the wrong physical cell for logical index {:?}",
+                    idx
+                );
+            }
+        }
+    }
+
+    /// Builds a 3x3 map whose `Layer0` cell values are `y * 3 + x + 1`, i.e. 1..=9 in raster
+    /// order, so `par_window_map`'s border modes have distinct values to tell clamped/defaulted
+    /// cells apart.
+    fn border_test_map() -> CellMap<TestLayers, f64> {
+        let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+            CellMapParams {
+                cell_bounds: Bounds::new((0, 3), (0, 3)).unwrap(),
+                cell_size: Vector2::new(1.0, 1.0),
+                ..Default::default()
+            },
+            0.0,
+        );
+
+        for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+            *v = (idx.y * 3 + idx.x + 1) as f64;
+        }
+
+        map
+    }
+
+    /// As [`border_test_map`], but scrolled by [`CellMap::move_by`] before its values are set, so
+    /// the regression test for `par_window_map` exercises a non-trivially wrapped physical layout.
+    fn border_test_map_after_move_by() -> CellMap<TestLayers, f64> {
+        let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+            CellMapParams {
+                cell_bounds: Bounds::new((0, 3), (0, 3)).unwrap(),
+                cell_size: Vector2::new(1.0, 1.0),
+                ..Default::default()
+            },
+            0.0,
+        );
+
+        map.move_by(Vector2::new(1, 2), 0.0);
+
+        for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+            *v = (idx.y * 3 + idx.x + 1) as f64;
+        }
+
+        map
+    }
+
+    #[test]
+    fn par_window_map_border_modes() {
+        let map = border_test_map();
+        let sum = |window: &Array2<f64>| window.iter().sum::<f64>();
+
+        // `Skip` leaves center cells within `radius` of the edge at the output's default value...
+        let skip = map.par_window_map(Vector2::new(1, 1), BorderMode::Skip, sum);
+        assert_eq!(
+            *skip.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+            0.0
+        );
+        // ...while the fully-in-bounds center cell is still evaluated.
+        assert_eq!(
+            *skip.get(TestLayers::Layer0, Point2::new(1, 1)).unwrap(),
+            45.0
+        );
+
+        // `Clamp` replicates the nearest in-bounds cell into every out-of-bounds window slot.
+        let clamp = map.par_window_map(Vector2::new(1, 1), BorderMode::Clamp, sum);
+        assert_eq!(
+            *clamp.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+            21.0
+        );
+
+        // `Constant` treats every out-of-bounds slot as the given value; the corner window has 4
+        // in-bounds cells (1 + 2 + 4 + 5 = 12) and 5 out-of-bounds ones filled with 99.0.
+        let constant = map.par_window_map(Vector2::new(1, 1), BorderMode::Constant(99.0), sum);
+        assert_eq!(
+            *constant.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+            12.0 + 5.0 * 99.0
+        );
+    }
+
+    #[test]
+    fn par_window_map_respects_ring_buffer_after_move_by() {
+        // Regression test: `par_window_map_layer`/`sample_window` read directly from the source
+        // map's raw physical storage, so they need the map's `start_index` to gather the right
+        // cells once it's been scrolled.
+        let map = border_test_map_after_move_by();
+
+        let sum = |window: &Array2<f64>| window.iter().sum::<f64>();
+        let clamp = map.par_window_map(Vector2::new(1, 1), BorderMode::Clamp, sum);
+
+        assert_eq!(
+            *clamp.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+            21.0
+        );
+        assert_eq!(
+            *clamp.get(TestLayers::Layer0, Point2::new(1, 1)).unwrap(),
+            45.0
+        );
+    }
+}
@@ -17,6 +17,11 @@ use crate::{extensions::Affine2Ext, iterators::Slicer, Layer};
 
 /// A [`Slicer`] which wrapps another [`Slicer`] and modifies it to produce the position of the item
 /// as well as the item itself.
+///
+/// The position is the continuous centre of the cell in the map's parent frame: for grid index
+/// `(x, y)` this is `(x + 0.5, y + 0.5)` scaled by `cell_size`, then rotated by
+/// `rotation_in_parent_rad` and offset by `position_in_parent`, via the map's `to_parent` affine
+/// transform.
 #[derive(Debug, Clone, Copy)]
 pub struct Positioned<'a, L, T, S>
 where
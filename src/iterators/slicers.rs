@@ -7,6 +7,9 @@
 // IMPORTS
 // ------------------------------------------------------------------------------------------------
 
+use std::collections::VecDeque;
+use std::ops::{Bound, RangeBounds};
+
 use nalgebra::{Point2, Vector2};
 use ndarray::{s, Array2, ArrayView2, ArrayViewMut2};
 use serde::Serialize;
@@ -60,25 +63,128 @@ where
     fn reset(&mut self, layer: Option<L>);
 }
 
+/// A [`Slicer`] whose traversal of a layer is fully determined by static bounds known up front,
+/// so the number of cells it will ever produce, and the reverse order in which it could produce
+/// them, can both be computed without running the iteration.
+///
+/// This is implemented by [`Cells`] and [`Windows`], which both do a plain raster scan over a
+/// fixed [`RectBounds`]. It's deliberately not implemented by slicers whose remaining length or
+/// traversal order depends on runtime state, such as [`Disk`] (depends on its `matches`
+/// predicate), [`Line`]/[`ThickLine`]/[`Polygon`] (depend on the geometry being walked), or
+/// [`Wavefront`] (depends on a user-supplied cost closure and isn't even [`Clone`]).
+pub trait ExactSlicer<'a, L, T>: Slicer<'a, L, T>
+where
+    L: Layer,
+{
+    /// The total number of cells a single full pass of this [`Slicer`] produces over one layer.
+    fn total(&self) -> usize;
+
+    /// The number of cells remaining to be produced between [`Slicer::index`] and
+    /// [`ExactSlicer::index_back`], inclusive, within the layer this slicer is currently
+    /// addressing.
+    fn remaining(&self) -> usize;
+
+    /// Returns the current back-cursor index, the reverse counterpart to [`Slicer::index`].
+    fn index_back(&self) -> Option<Point2<usize>>;
+
+    /// Slices the item at the back cursor, the reverse counterpart to [`Slicer::slice`].
+    fn slice_back(&self, data: &'a Array2<T>) -> Option<Self::Output>;
+
+    /// Mutable counterpart to [`ExactSlicer::slice_back`].
+    fn slice_mut_back(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut>;
+
+    /// Moves the back cursor one cell towards the front, the reverse counterpart to
+    /// [`Slicer::advance`].
+    fn advance_back(&mut self);
+}
+
 /// Rectangular bounds in an XY plane. Lower bound is inclusive, upper exclusive.
 pub(crate) type RectBounds = Vector2<(usize, usize)>;
 
+/// Converts a raster position known to lie within `bounds` into a 0-based linear offset into
+/// `bounds`, in the same `(x, y)` order as [`Cells`]/[`Windows`] raster over it (`x` fastest).
+///
+/// Used by [`ExactSlicer`] impls to compare how far apart the front and back cursors are.
+fn linear(bounds: &RectBounds, p: Point2<usize>) -> usize {
+    let width = bounds.x.1 - bounds.x.0;
+    (p.y - bounds.y.0) * width + (p.x - bounds.x.0)
+}
+
 /// A [`Slicer`] which produces cells in `(x, y)` order inside a layer, with `x` increasing most
 /// rapidly.
+///
+/// `index` tracks the logical position within the iteration, which is mapped to the physical
+/// storage index of the underlying `ndarray` via `start_index`/`num_cells`, so that maps using
+/// [`CellMap::move_by`]'s ring buffer still produce a contiguous logical view.
+///
+/// [`CellMap::move_by`]: crate::CellMap::move_by
 #[derive(Debug, Clone, Copy)]
 pub struct Cells {
     bounds: RectBounds,
     index: Point2<usize>,
+    /// The back cursor used by the [`ExactSlicer`] impl, starting at the last cell in `bounds`
+    /// and moving towards `index` as [`ExactSlicer::advance_back`] is called.
+    back_index: Point2<usize>,
+    start_index: Vector2<usize>,
+    num_cells: Vector2<usize>,
 }
 
 /// A [`Slicer`] which produces rectangular views into a layer in `(x, y)` order, increasing `x`
-/// most rapidly. A boundary of the `semi_width` of the window around the outside edge of the map
-/// is used to prevent indexing outside the map.
+/// most rapidly. A boundary around the outside edge of the map is used to prevent indexing
+/// outside the map.
+///
+/// Every window has the same `extent` (full `(width, height)`) and `anchor` (the offset within
+/// the window, from its `(0, 0)` corner, of the "current" cell that `index()` reports). The common
+/// symmetric case, built by [`Windows::from_map`], is just `extent = 2 * semi_width + 1, anchor =
+/// semi_width`; [`Windows::from_map_asym`] allows any other `extent`/`anchor` pair, including
+/// even-sized or off-center windows, for directional stencils and non-square tiling.
+///
+/// **Note:** unlike [`Cells`], this slicer does not account for
+/// [`CellMapMetadata::start_index`], since a window that straddles the ring buffer's wrap point
+/// can't be expressed as a single contiguous `ArrayView2`. Rather than silently returning
+/// wrongly-wrapped data, construction is refused with [`Error::WindowedIterOnScrolledMap`] if the
+/// map has ever been scrolled by [`CellMap::move_by`] or [`CellMap::move_to`] with a non-zero
+/// `start_index` as a result.
+///
+/// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+/// [`CellMap::move_by`]: crate::CellMap::move_by
+/// [`CellMap::move_to`]: crate::CellMap::move_to
 #[derive(Debug, Clone, Copy)]
 pub struct Windows {
     bounds: RectBounds,
     index: Point2<usize>,
-    semi_width: Vector2<usize>,
+    /// The back cursor used by the [`ExactSlicer`] impl, starting at the last cell in `bounds`
+    /// and moving towards `index` as [`ExactSlicer::advance_back`] is called.
+    back_index: Point2<usize>,
+    anchor: Vector2<usize>,
+    extent: Vector2<usize>,
+}
+
+/// The distance metric used by [`Disk`] to decide whether a cell is within range of its center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskMetric {
+    /// Euclidean (L2) distance.
+    Euclidean,
+    /// Manhattan (L1) distance, i.e. `|dx| + |dy|`, giving a diamond-shaped selection.
+    Manhattan,
+}
+
+/// A [`Slicer`] which produces every cell within `radius` of a center point, in `(x, y)` raster
+/// order.
+///
+/// At construction the disk's bounding box is computed in map coordinates and clamped to the
+/// map's extent; `advance()` then raster-scans that box, skipping any cell whose center-to-center
+/// distance to the (map-frame) center exceeds `radius` under the chosen [`DiskMetric`]. This is a
+/// cheaper, transform-aware alternative to [`Polygon`] for the common circular (or diamond)
+/// selection case.
+#[derive(Debug, Clone, Copy)]
+pub struct Disk {
+    bounds: RectBounds,
+    index: Point2<usize>,
+    center: Point2<f64>,
+    radius: f64,
+    metric: DiskMetric,
+    map_meta: CellMapMetadata,
 }
 
 /// A [`Slicer`] which produces cells along the line connecting two points in the parent frame.
@@ -108,6 +214,123 @@ pub struct Line {
     step_report_file: std::sync::Arc<std::fs::File>,
 }
 
+/// A [`Slicer`] which produces every cell within `half_width` cells of the line connecting two
+/// points in the parent frame, the width-parameterized counterpart to the single-cell-wide
+/// [`Line`].
+///
+/// Drives a [`Line`] along the centerline and, at each of its steps, emits the band of cells
+/// offset by `-half_width..=half_width` cells along the unit normal to the line's direction, in
+/// that order, before advancing the centerline. A `visited` mask (the map's full extent, as for
+/// [`Wavefront`]) deduplicates cells the band covers more than once, which happens wherever the
+/// corridor doubles back over itself relative to the grid, giving the same "every intersected
+/// cell exactly once" guarantee [`Line`] documents for the unwidened case.
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+pub struct ThickLine {
+    line: Line,
+    half_width: f64,
+    normal: Vector2<f64>,
+    visited: Array2<bool>,
+    queue: VecDeque<Point2<usize>>,
+    current: Option<Point2<usize>>,
+}
+
+/// A [`Slicer`] which produces every cell whose center lies inside an arbitrary closed polygon, in
+/// `(x, y)` raster order.
+///
+/// Implemented with scanline polygon fill: the polygon's vertices (given in the parent frame) are
+/// transformed into map-frame float coordinates once at construction, then for each scanline `y`
+/// (the row of cells whose centers sit at `y + 0.5`) every edge is tested for whether it crosses
+/// that line, using the half-open convention `min(y0, y1) <= y_scan < max(y0, y1)` so that shared
+/// vertices and horizontal edges are not double-counted. The resulting crossing x-coordinates are
+/// sorted and filled in pairs (the parity rule), giving the cell columns inside the polygon on
+/// that row.
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+pub struct Polygon {
+    bounds: RectBounds,
+    edges: Vec<(Point2<f64>, Point2<f64>)>,
+    y: usize,
+    row_xs: Vec<usize>,
+    row_idx: usize,
+    map_meta: CellMapMetadata,
+}
+
+/// The connectivity [`Wavefront`] uses when expanding the frontier from a popped cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WavefrontConnectivity {
+    /// Expand to the four edge-adjacent neighbors.
+    Four,
+    /// Expand to all eight edge- and corner-adjacent neighbors.
+    Eight,
+}
+
+/// An `f64` cost wrapped so it can be ordered inside a [`BinaryHeap`], which requires `Ord`.
+///
+/// Ordering falls back to `partial_cmp` and panics on `NaN`; traversal costs
+/// produced by [`Wavefront`]'s `step_cost` predicate are assumed to always be finite.
+///
+/// [`BinaryHeap`]: std::collections::BinaryHeap
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrderedCost(f64);
+
+impl Eq for OrderedCost {}
+
+impl PartialOrd for OrderedCost {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedCost {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).expect("NaN traversal cost")
+    }
+}
+
+/// A [`Slicer`] which produces cells in order of increasing accumulated travel cost from one or
+/// more seed cells, i.e. a Dijkstra / wavefront expansion.
+///
+/// A min-heap of `(cost, index)` entries (wrapped in [`Reverse`] since [`BinaryHeap`] is a
+/// max-heap) drives the expansion: `reset()` seeds the heap with every seed cell at cost `0.0`,
+/// and each `advance()` pops the lowest-cost unvisited entry, marks it visited in a `visited` mask
+/// sized to the map, and pushes its unvisited [`WavefrontConnectivity`] neighbors with
+/// `cost + step_cost(neighbor)`, skipping any neighbor for which `step_cost` returns `None`
+/// (blocked). Checking `visited` on pop rather than on push is what guarantees each cell is
+/// produced exactly once, with its minimal accumulated cost, even though a cell may be pushed onto
+/// the heap multiple times via different paths.
+///
+/// [`BinaryHeap`]: std::collections::BinaryHeap
+/// [`Reverse`]: std::cmp::Reverse
+#[allow(missing_copy_implementations)]
+pub struct Wavefront<F>
+where
+    F: Fn(Point2<usize>) -> Option<f64>,
+{
+    bounds: RectBounds,
+    connectivity: WavefrontConnectivity,
+    step_cost: F,
+    seeds: Vec<Point2<usize>>,
+    visited: Array2<bool>,
+    heap: std::collections::BinaryHeap<std::cmp::Reverse<(OrderedCost, usize, usize)>>,
+    current: Option<(Point2<usize>, f64)>,
+    map_meta: CellMapMetadata,
+}
+
+impl<F> std::fmt::Debug for Wavefront<F>
+where
+    F: Fn(Point2<usize>) -> Option<f64>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wavefront")
+            .field("bounds", &self.bounds)
+            .field("connectivity", &self.connectivity)
+            .field("seeds", &self.seeds)
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize)]
 struct LineStepData {
     start_parent: Point2<f64>,
@@ -125,6 +348,56 @@ struct LineStepData {
     delta: Vector2<f64>,
 }
 
+/// Converts an axis-aligned rectangle given by two opposite corners in the parent frame into a
+/// [`RectBounds`] of cell indices, clamped to the map's own extent. The corners need not be given
+/// in any particular order.
+fn region_bounds(
+    map_meta: CellMapMetadata,
+    corner_a: Point2<f64>,
+    corner_b: Point2<f64>,
+) -> RectBounds {
+    // Safety: the result is clamped to the map's own bounds below, so an out-of-map corner (which
+    // may come back negative) is handled rather than used to index anything directly.
+    let idx_a = unsafe { map_meta.index_unchecked(corner_a) };
+    let idx_b = unsafe { map_meta.index_unchecked(corner_b) };
+
+    let map_bounds = map_meta.get_bounds();
+
+    let clamp_axis = |lo: isize, hi: isize, map_lo: usize, map_hi: usize| -> (usize, usize) {
+        (
+            lo.clamp(map_lo as isize, map_hi as isize) as usize,
+            hi.clamp(map_lo as isize, map_hi as isize) as usize,
+        )
+    };
+
+    let x = clamp_axis(
+        idx_a.x.min(idx_b.x),
+        idx_a.x.max(idx_b.x) + 1,
+        map_bounds.x.0,
+        map_bounds.x.1,
+    );
+    let y = clamp_axis(
+        idx_a.y.min(idx_b.y),
+        idx_a.y.max(idx_b.y) + 1,
+        map_bounds.y.0,
+        map_bounds.y.1,
+    );
+
+    Vector2::new(x, y)
+}
+
+/// Returns the intersection of two [`RectBounds`], clamping each axis' lower bound so it never
+/// exceeds its upper bound.
+fn intersect_bounds(a: RectBounds, b: RectBounds) -> RectBounds {
+    let x0 = a.x.0.max(b.x.0);
+    let y0 = a.y.0.max(b.y.0);
+
+    Vector2::new(
+        (x0, a.x.1.min(b.x.1).max(x0)),
+        (y0, a.y.1.min(b.y.1).max(y0)),
+    )
+}
+
 // ------------------------------------------------------------------------------------------------
 // IMPLS
 // ------------------------------------------------------------------------------------------------
@@ -132,11 +405,54 @@ struct LineStepData {
 impl Cells {
     pub(crate) fn from_map<L: Layer, T>(map: &CellMap<L, T>) -> Self {
         let cells = map.num_cells();
+        let bounds = Vector2::new((0, cells.x), (0, cells.y));
         Self {
-            bounds: Vector2::new((0, cells.x), (0, cells.y)),
+            bounds,
             index: Point2::new(0, 0),
+            back_index: Self::back_of(bounds),
+            start_index: map.metadata.start_index,
+            num_cells: cells,
+        }
+    }
+
+    /// As [`Cells::from_map`], but restricted to the sub-rectangle of the map between `corner_a`
+    /// and `corner_b` (positions in the map's parent frame, in either order), clamped to the map's
+    /// own extent.
+    pub(crate) fn from_map_region<L: Layer, T>(
+        map: &CellMap<L, T>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Self {
+        let bounds = region_bounds(map.metadata, corner_a, corner_b);
+
+        Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            back_index: Self::back_of(bounds),
+            start_index: map.metadata.start_index,
+            num_cells: map.num_cells(),
         }
     }
+
+    /// Returns the last cell in `bounds`, the initial value of `back_index`.
+    fn back_of(bounds: RectBounds) -> Point2<usize> {
+        Point2::new(bounds.x.1.saturating_sub(1), bounds.y.1.saturating_sub(1))
+    }
+
+    /// Maps the given logical index to the physical storage index it lives at, accounting for the
+    /// ring buffer's `start_index`.
+    fn physical_index_of(&self, index: Point2<usize>) -> Point2<usize> {
+        Point2::new(
+            (index.x + self.start_index.x) % self.num_cells.x,
+            (index.y + self.start_index.y) % self.num_cells.y,
+        )
+    }
+
+    /// Maps the current logical `index` to the physical storage index it lives at, accounting
+    /// for the ring buffer's `start_index`.
+    fn physical_index(&self) -> Point2<usize> {
+        self.physical_index_of(self.index)
+    }
 }
 
 impl<'a, L, T> Slicer<'a, L, T> for Cells
@@ -148,11 +464,11 @@ where
     type OutputMut = &'a mut T;
 
     fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
-        data.get(self.index.as_array2_index())
+        data.get(self.physical_index().as_array2_index())
     }
 
     fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
-        data.get_mut(self.index.as_array2_index())
+        data.get_mut(self.physical_index().as_array2_index())
     }
 
     fn advance(&mut self) {
@@ -174,6 +490,58 @@ where
 
     fn reset(&mut self, _layer: Option<L>) {
         self.index = Point2::new(self.bounds.x.0, self.bounds.y.0);
+        self.back_index = Self::back_of(self.bounds);
+    }
+}
+
+impl<'a, L, T> ExactSlicer<'a, L, T> for Cells
+where
+    L: Layer,
+    T: 'a,
+{
+    fn total(&self) -> usize {
+        (self.bounds.x.1 - self.bounds.x.0) * (self.bounds.y.1 - self.bounds.y.0)
+    }
+
+    fn remaining(&self) -> usize {
+        if self.index.in_bounds(&self.bounds) && self.back_index.in_bounds(&self.bounds) {
+            let front = linear(&self.bounds, self.index);
+            let back = linear(&self.bounds, self.back_index);
+            if front <= back {
+                return back - front + 1;
+            }
+        }
+        0
+    }
+
+    fn index_back(&self) -> Option<Point2<usize>> {
+        if self.index.in_bounds(&self.bounds)
+            && self.back_index.in_bounds(&self.bounds)
+            && linear(&self.bounds, self.index) <= linear(&self.bounds, self.back_index)
+        {
+            Some(self.back_index)
+        } else {
+            None
+        }
+    }
+
+    fn slice_back(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        self.index_back()?;
+        data.get(self.physical_index_of(self.back_index).as_array2_index())
+    }
+
+    fn slice_mut_back(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        self.index_back()?;
+        data.get_mut(self.physical_index_of(self.back_index).as_array2_index())
+    }
+
+    fn advance_back(&mut self) {
+        if self.back_index.x > self.bounds.x.0 {
+            self.back_index.x -= 1;
+        } else {
+            self.back_index.y = self.back_index.y.wrapping_sub(1);
+            self.back_index.x = self.bounds.x.1.saturating_sub(1);
+        }
     }
 }
 
@@ -182,25 +550,119 @@ impl Windows {
         map: &CellMap<L, T>,
         semi_width: Vector2<usize>,
     ) -> Result<Self, Error> {
-        let cells = map.num_cells();
+        Self::from_map_asym(map, semi_width * 2 + Vector2::new(1, 1), semi_width)
+    }
 
-        if semi_width.x * 2 + 1 > cells.x || semi_width.y * 2 + 1 > cells.y {
-            Err(Error::WindowLargerThanMap(
-                semi_width * 2 + Vector2::new(1, 1),
-                cells,
-            ))
-        } else {
-            let bounds = Vector2::new(
-                (semi_width.x, cells.x - semi_width.x),
-                (semi_width.y, cells.y - semi_width.y),
-            );
-
-            Ok(Self {
-                bounds,
-                index: Point2::new(bounds.x.0, bounds.y.0),
-                semi_width,
-            })
+    /// As [`Windows::from_map`], but restricted to the sub-rectangle of the map between
+    /// `corner_a` and `corner_b` (positions in the map's parent frame, in either order), clamped
+    /// to the map's own extent and to the margin `semi_width` needs kept clear of the map edge.
+    pub(crate) fn from_map_region<L: Layer, T>(
+        map: &CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<Self, Error> {
+        Self::from_map_region_asym(
+            map,
+            semi_width * 2 + Vector2::new(1, 1),
+            semi_width,
+            corner_a,
+            corner_b,
+        )
+    }
+
+    /// As [`Windows::from_map`], but for an arbitrary window `extent` (full `(width, height)`) and
+    /// `anchor` (the offset within the window, from its `(0, 0)` corner, of the "current" cell
+    /// `index()` reports), rather than the symmetric, centred `2 * semi_width + 1` window
+    /// `from_map` builds. This allows even-sized windows and off-center anchors, e.g. a `(2, 1)`
+    /// extent with a `(0, 0)` anchor for a forward-difference gradient.
+    ///
+    /// Returns [`Error::InvalidWindowAnchor`] if `anchor` doesn't lie inside `extent`,
+    /// [`Error::WindowLargerThanMap`] if `extent` is larger than the map itself, or
+    /// [`Error::WindowedIterOnScrolledMap`] if the map has a non-zero
+    /// [`CellMapMetadata::start_index`].
+    ///
+    /// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+    pub(crate) fn from_map_asym<L: Layer, T>(
+        map: &CellMap<L, T>,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<Self, Error> {
+        if map.metadata.start_index != Vector2::zeros() {
+            return Err(Error::WindowedIterOnScrolledMap(map.metadata.start_index));
+        }
+
+        let bounds = Self::bounds(map.num_cells(), extent, anchor)?;
+
+        Ok(Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            back_index: Self::back_of(bounds),
+            anchor,
+            extent,
+        })
+    }
+
+    /// As [`Windows::from_map_asym`], but restricted to the sub-rectangle of the map between
+    /// `corner_a` and `corner_b` (positions in the map's parent frame, in either order), clamped
+    /// to the map's own extent and to the margin the window needs kept clear of the map edge.
+    ///
+    /// Returns [`Error::WindowedIterOnScrolledMap`] if the map has a non-zero
+    /// [`CellMapMetadata::start_index`].
+    ///
+    /// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+    pub(crate) fn from_map_region_asym<L: Layer, T>(
+        map: &CellMap<L, T>,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+        corner_a: Point2<f64>,
+        corner_b: Point2<f64>,
+    ) -> Result<Self, Error> {
+        if map.metadata.start_index != Vector2::zeros() {
+            return Err(Error::WindowedIterOnScrolledMap(map.metadata.start_index));
+        }
+
+        let margin_bounds = Self::bounds(map.num_cells(), extent, anchor)?;
+        let bounds = intersect_bounds(
+            margin_bounds,
+            region_bounds(map.metadata, corner_a, corner_b),
+        );
+
+        Ok(Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            back_index: Self::back_of(bounds),
+            anchor,
+            extent,
+        })
+    }
+
+    /// Validates `extent`/`anchor` and returns the range of indices a window with them can be
+    /// centred on without going outside a map of `cells` cells.
+    fn bounds(
+        cells: Vector2<usize>,
+        extent: Vector2<usize>,
+        anchor: Vector2<usize>,
+    ) -> Result<RectBounds, Error> {
+        if anchor.x >= extent.x || anchor.y >= extent.y {
+            return Err(Error::InvalidWindowAnchor(anchor, extent));
+        }
+
+        if extent.x > cells.x || extent.y > cells.y {
+            return Err(Error::WindowLargerThanMap(extent, cells));
         }
+
+        let trailing = Vector2::new(extent.x - anchor.x - 1, extent.y - anchor.y - 1);
+
+        Ok(Vector2::new(
+            (anchor.x, cells.x - trailing.x),
+            (anchor.y, cells.y - trailing.y),
+        ))
+    }
+
+    /// Returns the last cell in `bounds`, the initial value of `back_index`.
+    fn back_of(bounds: RectBounds) -> Point2<usize> {
+        Point2::new(bounds.x.1.saturating_sub(1), bounds.y.1.saturating_sub(1))
     }
 }
 
@@ -214,10 +676,10 @@ where
 
     fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
         if self.index.in_bounds(&self.bounds) {
-            let x0 = self.index.x - self.semi_width.x;
-            let x1 = self.index.x + self.semi_width.x + 1;
-            let y0 = self.index.y - self.semi_width.y;
-            let y1 = self.index.y + self.semi_width.y + 1;
+            let x0 = self.index.x - self.anchor.x;
+            let x1 = x0 + self.extent.x;
+            let y0 = self.index.y - self.anchor.y;
+            let y1 = y0 + self.extent.y;
             Some(data.slice(s![y0..y1, x0..x1]))
         } else {
             None
@@ -226,10 +688,10 @@ where
 
     fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
         if self.index.in_bounds(&self.bounds) {
-            let x0 = self.index.x - self.semi_width.x;
-            let x1 = self.index.x + self.semi_width.x + 1;
-            let y0 = self.index.y - self.semi_width.y;
-            let y1 = self.index.y + self.semi_width.y + 1;
+            let x0 = self.index.x - self.anchor.x;
+            let x1 = x0 + self.extent.x;
+            let y0 = self.index.y - self.anchor.y;
+            let y1 = y0 + self.extent.y;
             Some(data.slice_mut(s![y0..y1, x0..x1]))
         } else {
             None
@@ -255,127 +717,577 @@ where
 
     fn reset(&mut self, _layer: Option<L>) {
         self.index = Point2::new(self.bounds.x.0, self.bounds.y.0);
+        self.back_index = Self::back_of(self.bounds);
     }
 }
 
-impl Line {
-    pub(crate) fn from_map<L: Layer, T>(
-        map_meta: CellMapMetadata,
-        start_parent: Point2<f64>,
-        end_parent: Point2<f64>,
-    ) -> Result<Self, Error> {
-        // Calculate start and end points in map frame, note these aren't cell indices, instead
-        // they are floating point positions within the map frame, which we get by not casting the
-        // output of the `to_parent` transforms to usize.
-        let start_map = map_meta.to_parent.inverse_transform_point(&start_parent);
-        let end_map = map_meta.to_parent.inverse_transform_point(&end_parent);
+impl<'a, L, T> ExactSlicer<'a, L, T> for Windows
+where
+    L: Layer,
+    T: 'a,
+{
+    fn total(&self) -> usize {
+        (self.bounds.x.1 - self.bounds.x.0) * (self.bounds.y.1 - self.bounds.y.0)
+    }
 
-        // Get map edges in floating point for bounds check
-        let map_x_lim = (map_meta.num_cells.x) as f64;
-        let map_y_lim = (map_meta.num_cells.y) as f64;
+    fn remaining(&self) -> usize {
+        if self.index.in_bounds(&self.bounds) && self.back_index.in_bounds(&self.bounds) {
+            let front = linear(&self.bounds, self.index);
+            let back = linear(&self.bounds, self.back_index);
+            if front <= back {
+                return back - front + 1;
+            }
+        }
+        0
+    }
 
-        // Check start and end points are inside the map
-        if start_map.x < 0.0
-            || start_map.x > map_x_lim
-            || start_map.y < 0.0
-            || start_map.y > map_y_lim
+    fn index_back(&self) -> Option<Point2<usize>> {
+        if self.index.in_bounds(&self.bounds)
+            && self.back_index.in_bounds(&self.bounds)
+            && linear(&self.bounds, self.index) <= linear(&self.bounds, self.back_index)
         {
-            return Err(Error::PositionOutsideMap(
-                "Line::Start".into(),
-                start_parent,
-            ));
+            Some(self.back_index)
+        } else {
+            None
         }
+    }
 
-        if end_map.x < 0.0 || end_map.x > map_x_lim || end_map.y < 0.0 || end_map.y > map_y_lim {
-            return Err(Error::PositionOutsideMap("Line::End".into(), start_parent));
-        }
+    fn slice_back(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        self.index_back()?;
+        let x0 = self.back_index.x - self.anchor.x;
+        let x1 = x0 + self.extent.x;
+        let y0 = self.back_index.y - self.anchor.y;
+        let y1 = y0 + self.extent.y;
+        Some(data.slice(s![y0..y1, x0..x1]))
+    }
 
-        // Calculate direction vector
-        let dir = end_map - start_map;
+    fn slice_mut_back(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        self.index_back()?;
+        let x0 = self.back_index.x - self.anchor.x;
+        let x1 = x0 + self.extent.x;
+        let y0 = self.back_index.y - self.anchor.y;
+        let y1 = y0 + self.extent.y;
+        Some(data.slice_mut(s![y0..y1, x0..x1]))
+    }
 
-        // Get the direction sign
-        let dir_sign = dir.map(|v| if v < 0.0 { 0.0 } else { 1.0 });
+    fn advance_back(&mut self) {
+        if self.back_index.x > self.bounds.x.0 {
+            self.back_index.x -= 1;
+        } else {
+            self.back_index.y = self.back_index.y.wrapping_sub(1);
+            self.back_index.x = self.bounds.x.1.saturating_sub(1);
+        }
+    }
+}
 
-        // Get the cell index of the end point
-        let end_cell = map_meta
-            .index(end_parent)
-            .ok_or_else(|| Error::PositionOutsideMap("Line::End".into(), end_parent))?;
+/// A [`Slicer`] which produces cells in `(x, y)` order, x increasing most rapidly, inside an
+/// arbitrary rectangular sub-view of a layer described by independent
+/// [`RangeBounds<usize>`](std::ops::RangeBounds) on each axis.
+///
+/// This is the cell-index counterpart to [`Cells::from_map_region`], which instead takes corner
+/// positions in the parent frame; `SubGrid` is for callers who already know which indices they
+/// want, e.g. the footprint of a sensor or a vehicle computed elsewhere. Each bound is resolved
+/// the same way as `(Bound, Bound)` slice indexing in the standard library, then clamped to the
+/// map's own [`CellMap::num_cells`], so an out-of-range bound is shrunk to fit rather than
+/// erroring.
+///
+/// [`CellMap::num_cells`]: crate::CellMap::num_cells
+#[derive(Debug, Clone, Copy)]
+pub struct SubGrid {
+    bounds: RectBounds,
+    index: Point2<usize>,
+    start_index: Vector2<usize>,
+    num_cells: Vector2<usize>,
+}
 
-        Ok(Self {
-            bounds: map_meta.get_bounds(),
-            map_meta,
-            start_parent,
-            end_parent,
-            dir,
-            dir_sign,
-            start_map,
-            end_map,
-            current_map: Some(start_map),
-            end_index: end_cell,
-            #[cfg(feature = "debug_iters")]
-            step_report_file: std::sync::Arc::new(
-                std::fs::OpenOptions::new()
-                    .create(true)
-                    .truncate(true)
-                    .write(true)
-                    .open("line_step_report.json")
-                    .unwrap(),
-            ),
-        })
+impl SubGrid {
+    pub(crate) fn from_map<L: Layer, T>(
+        map: &CellMap<L, T>,
+        x: impl RangeBounds<usize>,
+        y: impl RangeBounds<usize>,
+    ) -> Self {
+        let cells = map.num_cells();
+        let bounds = Vector2::new(resolve_axis(x, cells.x), resolve_axis(y, cells.y));
+
+        Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            start_index: map.metadata.start_index,
+            num_cells: cells,
+        }
     }
 
-    /// Gets the current cell index to yield, or `None` if at the end of the line
-    fn get_current_index(&self) -> Option<Point2<usize>> {
-        // Current will be inside the map, since start and end were confirmed to be inside the map
-        // at construction, so simply cast
-        Some(self.current_map?.map(|v| v as usize))
+    /// Maps the current logical `index` to the physical storage index it lives at, accounting
+    /// for the ring buffer's `start_index`, same as [`Cells::physical_index`].
+    fn physical_index(&self) -> Point2<usize> {
+        Point2::new(
+            (self.index.x + self.start_index.x) % self.num_cells.x,
+            (self.index.y + self.start_index.y) % self.num_cells.y,
+        )
     }
 }
 
-impl<'a, L, T> Slicer<'a, L, T> for Line
+impl<'a, L, T> Slicer<'a, L, T> for SubGrid
 where
     L: Layer,
     T: 'a,
 {
     type Output = &'a T;
-
     type OutputMut = &'a mut T;
 
     fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
-        // Get the index
-        let index = self.get_current_index()?;
-
-        data.get(index.as_array2_index())
+        data.get(self.physical_index().as_array2_index())
     }
 
     fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
-        // Get the index
-        let index = self.get_current_index()?;
-
-        data.get_mut(index.as_array2_index())
+        data.get_mut(self.physical_index().as_array2_index())
     }
 
     fn advance(&mut self) {
-        // Get the index of the current position, or just return if we're at the end
-        let curr_index = match self.get_current_index() {
-            Some(i) => i,
-            None => return,
-        };
+        self.index.x += 1;
 
-        // Calculate the param value, i.e. how far along the line we are. If it > 1 we're at the end
-        let param = (self.current_map.unwrap() - self.start_map).norm()
-            / (self.end_map - self.start_map).norm();
-        if param > 1.0 {
-            self.current_map = None;
-            return;
+        if !self.index.in_bounds(&self.bounds) {
+            self.index.y += 1;
+            self.index.x = self.bounds.x.0;
+        }
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        if self.index.in_bounds(&self.bounds) {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.index = Point2::new(self.bounds.x.0, self.bounds.y.0);
+    }
+}
+
+/// Resolves a [`RangeBounds<usize>`](std::ops::RangeBounds) into a half-open `(min, max)` pair,
+/// clamped to `0..max_end`.
+///
+/// `Unbounded` resolves to the corresponding edge of `0..max_end`. Otherwise each bound is turned
+/// into the half-open convention [`RectBounds`] uses: an `Included` start maps to `n`, an
+/// `Excluded` start to `n + 1`, an `Included` end to `n + 1`, and an `Excluded` end to `n`. The
+/// result is then clamped so it always satisfies `0 <= min <= max <= max_end`, shrinking rather
+/// than erroring on an out-of-range bound.
+fn resolve_axis(range: impl RangeBounds<usize>, max_end: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.saturating_add(1),
+        Bound::Unbounded => 0,
+    }
+    .min(max_end);
+
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.saturating_add(1),
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => max_end,
+    }
+    .clamp(start, max_end);
+
+    (start, end)
+}
+
+/// Controls how [`PaddedWindows`] handles the parts of a window which fall outside the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPadding {
+    /// Don't produce a window for cells within `semi_width` of the map edge, same as [`Windows`].
+    Skip,
+
+    /// Treat out-of-bounds cells as `T::default()`.
+    Zero,
+
+    /// Treat out-of-bounds cells as having the value of the nearest in-bounds cell.
+    Clamp,
+
+    /// Treat out-of-bounds cells as a mirror image of the map across the edge, without repeating
+    /// the edge cell.
+    Reflect,
+
+    /// Treat out-of-bounds cells as wrapping around to the opposite edge of the map, for toroidal
+    /// maps.
+    Wrap,
+}
+
+/// Reflects `v` into `0..n` without repeating the `0`/`n - 1` edge cells, looping the reflection
+/// as many times as needed if `v` is more than one map-width out of bounds.
+fn reflect_index(v: isize, n: usize) -> usize {
+    if n <= 1 {
+        return 0;
+    }
+
+    let period = 2 * n as isize;
+    let m = v.rem_euclid(period);
+
+    (if m >= n as isize { period - 1 - m } else { m }) as usize
+}
+
+/// Wraps `v` into `0..n`, for toroidal maps.
+fn wrap_index(v: isize, n: usize) -> usize {
+    v.rem_euclid(n as isize) as usize
+}
+
+/// Clamps `v` into `0..n`, replicating the nearest edge cell.
+fn clamp_index(v: isize, n: usize) -> usize {
+    v.clamp(0, n as isize - 1) as usize
+}
+
+/// A [`Slicer`] which produces a window of cells around every cell in a map, in `(x, y)` order, x
+/// increasing most rapidly.
+///
+/// Unlike [`Windows`], which excludes the `semi_width` border around the map edge, `PaddedWindows`
+/// produces a window for *every* cell (unless constructed with [`WindowPadding::Skip`], which
+/// matches `Windows`' behaviour), synthesising the out-of-bounds parts of edge windows according
+/// to its [`WindowPadding`]. Because those synthesised cells aren't contiguous with the rest of
+/// the map's storage, each window is an owned [`Array2<T>`] rather than a borrowed
+/// [`ArrayView2`], which is the trade-off for always getting a full-size window.
+///
+/// Since every window it produces is already an owned copy, this slicer (unlike [`Windows`])
+/// correctly accounts for [`CellMapMetadata::start_index`]: the physical storage index for each
+/// sampled cell, in bounds or synthesised, is computed via the same wrap formula
+/// [`CellMapMetadata::wrap_index`] uses, so a scrolled map is read correctly.
+///
+/// [`CellMapMetadata::start_index`]: crate::map_metadata::CellMapMetadata::start_index
+/// [`CellMapMetadata::wrap_index`]: crate::map_metadata::CellMapMetadata::wrap_index
+#[derive(Debug, Clone, Copy)]
+pub struct PaddedWindows {
+    bounds: RectBounds,
+    index: Point2<usize>,
+    semi_width: Vector2<usize>,
+    num_cells: Vector2<usize>,
+    start_index: Vector2<usize>,
+    padding: WindowPadding,
+}
+
+impl PaddedWindows {
+    pub(crate) fn from_map<L: Layer, T>(
+        map: &CellMap<L, T>,
+        semi_width: Vector2<usize>,
+        padding: WindowPadding,
+    ) -> Result<Self, Error> {
+        let cells = map.num_cells();
+
+        let bounds = match padding {
+            WindowPadding::Skip => {
+                if semi_width.x * 2 + 1 > cells.x || semi_width.y * 2 + 1 > cells.y {
+                    return Err(Error::WindowLargerThanMap(
+                        semi_width * 2 + Vector2::new(1, 1),
+                        cells,
+                    ));
+                }
+
+                Vector2::new(
+                    (semi_width.x, cells.x - semi_width.x),
+                    (semi_width.y, cells.y - semi_width.y),
+                )
+            }
+            _ => Vector2::new((0, cells.x), (0, cells.y)),
+        };
+
+        Ok(Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            semi_width,
+            num_cells: cells,
+            start_index: map.metadata.start_index,
+            padding,
+        })
+    }
+
+    /// Maps a logical, in-map cell index to the physical storage index it lives at, accounting
+    /// for the ring buffer's `start_index`.
+    fn physical_index(&self, x: usize, y: usize) -> (usize, usize) {
+        (
+            (x + self.start_index.x) % self.num_cells.x,
+            (y + self.start_index.y) % self.num_cells.y,
+        )
+    }
+}
+
+impl<'a, L, T> Slicer<'a, L, T> for PaddedWindows
+where
+    L: Layer,
+    T: Clone + Default,
+{
+    type Output = Array2<T>;
+    type OutputMut = Array2<T>;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        if !self.index.in_bounds(&self.bounds) {
+            return None;
+        }
+
+        let shape = (self.semi_width.y * 2 + 1, self.semi_width.x * 2 + 1);
+
+        Some(Array2::from_shape_fn(shape, |(wy, wx)| {
+            let sx = self.index.x as isize + wx as isize - self.semi_width.x as isize;
+            let sy = self.index.y as isize + wy as isize - self.semi_width.y as isize;
+
+            if sx >= 0
+                && sy >= 0
+                && (sx as usize) < self.num_cells.x
+                && (sy as usize) < self.num_cells.y
+            {
+                let (px, py) = self.physical_index(sx as usize, sy as usize);
+                data[[py, px]].clone()
+            } else {
+                match self.padding {
+                    WindowPadding::Skip => {
+                        unreachable!("Skip-mode bounds keep every window fully in bounds")
+                    }
+                    WindowPadding::Zero => T::default(),
+                    WindowPadding::Clamp => {
+                        let cx = clamp_index(sx, self.num_cells.x);
+                        let cy = clamp_index(sy, self.num_cells.y);
+                        let (px, py) = self.physical_index(cx, cy);
+                        data[[py, px]].clone()
+                    }
+                    WindowPadding::Reflect => {
+                        let cx = reflect_index(sx, self.num_cells.x);
+                        let cy = reflect_index(sy, self.num_cells.y);
+                        let (px, py) = self.physical_index(cx, cy);
+                        data[[py, px]].clone()
+                    }
+                    WindowPadding::Wrap => {
+                        let cx = wrap_index(sx, self.num_cells.x);
+                        let cy = wrap_index(sy, self.num_cells.y);
+                        let (px, py) = self.physical_index(cx, cy);
+                        data[[py, px]].clone()
+                    }
+                }
+            }
+        }))
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        self.slice(data)
+    }
+
+    fn advance(&mut self) {
+        self.index.x += 1;
+
+        if !self.index.in_bounds(&self.bounds) {
+            self.index.y += 1;
+            self.index.x = self.bounds.x.0;
+        }
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        if self.index.in_bounds(&self.bounds) {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.index = Point2::new(self.bounds.x.0, self.bounds.y.0);
+    }
+}
+
+impl Disk {
+    pub(crate) fn from_map<L: Layer, T>(
+        map_meta: CellMapMetadata,
+        center_parent: Point2<f64>,
+        radius: f64,
+        metric: DiskMetric,
+    ) -> Self {
+        let center = map_meta.to_parent.inverse_transform_point(&center_parent);
+        let map_bounds = map_meta.get_bounds();
+
+        let x0 = (((center.x - radius).floor() as isize) - 1).max(map_bounds.x.0 as isize);
+        let x1 = (((center.x + radius).ceil() as isize) + 1).min(map_bounds.x.1 as isize);
+        let y0 = (((center.y - radius).floor() as isize) - 1).max(map_bounds.y.0 as isize);
+        let y1 = (((center.y + radius).ceil() as isize) + 1).min(map_bounds.y.1 as isize);
+
+        let bounds = Vector2::new(
+            (x0.max(0) as usize, x1.max(0) as usize),
+            (y0.max(0) as usize, y1.max(0) as usize),
+        );
+
+        let mut slicer = Self {
+            bounds,
+            index: Point2::new(bounds.x.0, bounds.y.0),
+            center,
+            radius,
+            metric,
+            map_meta,
+        };
+        slicer.seek_valid();
+
+        slicer
+    }
+
+    /// Returns whether `index`'s cell center is within `radius` of the disk's center.
+    fn matches(&self, index: Point2<usize>) -> bool {
+        let dx = (index.x as f64 + 0.5) - self.center.x;
+        let dy = (index.y as f64 + 0.5) - self.center.y;
+
+        match self.metric {
+            DiskMetric::Euclidean => (dx * dx + dy * dy).sqrt() <= self.radius,
+            DiskMetric::Manhattan => dx.abs() + dy.abs() <= self.radius,
+        }
+    }
+
+    /// Steps `self.index` to the next cell in raster order within `self.bounds`, without regard
+    /// to whether it matches the disk.
+    fn step_raster(&mut self) {
+        self.index.x += 1;
+
+        if !self.index.in_bounds(&self.bounds) {
+            self.index.y += 1;
+            self.index.x = self.bounds.x.0;
+        }
+    }
+
+    /// Steps `self.index` forward (without moving it if it already matches) until it matches the
+    /// disk or leaves `self.bounds`.
+    fn seek_valid(&mut self) {
+        while self.index.in_bounds(&self.bounds) && !self.matches(self.index) {
+            self.step_raster();
+        }
+    }
+}
+
+impl<'a, L, T> Slicer<'a, L, T> for Disk
+where
+    L: Layer,
+    T: 'a,
+{
+    type Output = &'a T;
+
+    type OutputMut = &'a mut T;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        if self.index.in_bounds(&self.bounds) {
+            data.get(self.map_meta.wrap_index(self.index).as_array2_index())
+        } else {
+            None
+        }
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        if self.index.in_bounds(&self.bounds) {
+            data.get_mut(self.map_meta.wrap_index(self.index).as_array2_index())
+        } else {
+            None
+        }
+    }
+
+    fn advance(&mut self) {
+        self.step_raster();
+        self.seek_valid();
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        if self.index.in_bounds(&self.bounds) {
+            Some(self.index)
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.index = Point2::new(self.bounds.x.0, self.bounds.y.0);
+        self.seek_valid();
+    }
+}
+
+impl Line {
+    pub(crate) fn from_map<L: Layer, T>(
+        map_meta: CellMapMetadata,
+        start_parent: Point2<f64>,
+        end_parent: Point2<f64>,
+    ) -> Result<Self, Error> {
+        // Calculate start and end points in map frame, note these aren't cell indices, instead
+        // they are floating point positions within the map frame, which we get by not casting the
+        // output of the `to_parent` transforms to usize.
+        let start_map = map_meta.to_parent.inverse_transform_point(&start_parent);
+        let end_map = map_meta.to_parent.inverse_transform_point(&end_parent);
+
+        // Get map edges in floating point for bounds check
+        let map_x_lim = (map_meta.num_cells.x) as f64;
+        let map_y_lim = (map_meta.num_cells.y) as f64;
+
+        // Check start and end points are inside the map
+        if start_map.x < 0.0
+            || start_map.x > map_x_lim
+            || start_map.y < 0.0
+            || start_map.y > map_y_lim
+        {
+            return Err(Error::PositionOutsideMap(
+                "Line::Start".into(),
+                start_parent,
+            ));
+        }
+
+        if end_map.x < 0.0 || end_map.x > map_x_lim || end_map.y < 0.0 || end_map.y > map_y_lim {
+            return Err(Error::PositionOutsideMap("Line::End".into(), start_parent));
+        }
+
+        // Calculate direction vector
+        let dir = end_map - start_map;
+
+        // Get the direction sign
+        let dir_sign = dir.map(|v| if v < 0.0 { 0.0 } else { 1.0 });
+
+        // Get the cell index of the end point
+        let end_cell = map_meta
+            .index(end_parent)
+            .ok_or_else(|| Error::PositionOutsideMap("Line::End".into(), end_parent))?;
+
+        Ok(Self {
+            bounds: map_meta.get_bounds(),
+            map_meta,
+            start_parent,
+            end_parent,
+            dir,
+            dir_sign,
+            start_map,
+            end_map,
+            current_map: Some(start_map),
+            end_index: end_cell,
+            #[cfg(feature = "debug_iters")]
+            step_report_file: std::sync::Arc::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .truncate(true)
+                    .write(true)
+                    .open("line_step_report.json")
+                    .unwrap(),
+            ),
+        })
+    }
+
+    /// Gets the current cell index to yield, or `None` if at the end of the line
+    fn get_current_index(&self) -> Option<Point2<usize>> {
+        // Current will be inside the map, since start and end were confirmed to be inside the map
+        // at construction, so simply cast
+        Some(self.current_map?.map(|v| v as usize))
+    }
+
+    /// Steps the line's marching position on to the next cell along its direction, or leaves it
+    /// at `None` if the line has already ended. Pulled out of the [`Slicer::advance`] impl so that
+    /// [`ThickLine`] can drive the same centerline march without needing a `Layer`/data type to
+    /// name the trait call.
+    fn step(&mut self) {
+        // Get the index of the current position, or just return if we're at the end
+        let curr_index = match self.get_current_index() {
+            Some(i) => i,
+            None => return,
+        };
+
+        // Calculate the param value, i.e. how far along the line we are. If it > 1 we're at the end
+        let param = (self.current_map.unwrap() - self.start_map).norm()
+            / (self.end_map - self.start_map).norm();
+        if param > 1.0 {
+            self.current_map = None;
+            return;
         }
 
-        // // If the current index matches the end cell, we are at the end, and set current to None
-        // if curr_index == self.end_index {
-        //     self.current_map = None;
-        //     return;
-        // }
-
         // Calculate the changes in the line parameter needed to reach the next x and y grid line
         // respectively. Also add on the cell boundary precision to ensure that we will actually
         // move over the cell boundary line.
@@ -417,6 +1329,34 @@ where
             .unwrap();
         }
     }
+}
+
+impl<'a, L, T> Slicer<'a, L, T> for Line
+where
+    L: Layer,
+    T: 'a,
+{
+    type Output = &'a T;
+
+    type OutputMut = &'a mut T;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        // Get the index
+        let index = self.get_current_index()?;
+
+        data.get(self.map_meta.wrap_index(index).as_array2_index())
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        // Get the index
+        let index = self.get_current_index()?;
+
+        data.get_mut(self.map_meta.wrap_index(index).as_array2_index())
+    }
+
+    fn advance(&mut self) {
+        self.step();
+    }
 
     fn index(&self) -> Option<Point2<usize>> {
         self.get_current_index()
@@ -426,3 +1366,382 @@ where
         self.current_map = Some(self.start_map)
     }
 }
+
+impl ThickLine {
+    pub(crate) fn from_map<L: Layer, T>(
+        map_meta: CellMapMetadata,
+        start_parent: Point2<f64>,
+        end_parent: Point2<f64>,
+        half_width: f64,
+    ) -> Result<Self, Error> {
+        let line = Line::from_map::<L, T>(map_meta, start_parent, end_parent)?;
+        let normal = Self::unit_normal(line.dir);
+        let bounds = map_meta.get_bounds();
+
+        let mut slicer = Self {
+            line,
+            half_width,
+            normal,
+            visited: Array2::from_elem((bounds.y.1, bounds.x.1), false),
+            queue: VecDeque::new(),
+            current: None,
+        };
+        slicer.advance();
+
+        Ok(slicer)
+    }
+
+    /// The unit vector perpendicular to `dir`, or the zero vector if `dir` has no length.
+    fn unit_normal(dir: Vector2<f64>) -> Vector2<f64> {
+        let perp = Vector2::new(-dir.y, dir.x);
+        let len = perp.norm();
+
+        if len > 0.0 {
+            perp / len
+        } else {
+            Vector2::zeros()
+        }
+    }
+
+    /// Pushes the band of unvisited, in-bounds cells around the line's current centerline cell
+    /// onto `queue`, marking each as visited as it's pushed.
+    fn fill_queue(&mut self) {
+        let Some(center_index) = self.line.get_current_index() else {
+            return;
+        };
+
+        let center = Point2::new(center_index.x as f64 + 0.5, center_index.y as f64 + 0.5);
+        let half_steps = self.half_width.ceil() as isize;
+
+        for k in -half_steps..=half_steps {
+            if (k as f64).abs() > self.half_width {
+                continue;
+            }
+
+            let offset = center + self.normal * k as f64;
+            let candidate = Point2::new(offset.x.floor(), offset.y.floor());
+
+            if candidate.x < 0.0 || candidate.y < 0.0 {
+                continue;
+            }
+
+            let index = Point2::new(candidate.x as usize, candidate.y as usize);
+
+            if !index.in_bounds(&self.line.bounds) || self.visited[[index.y, index.x]] {
+                continue;
+            }
+
+            self.visited[[index.y, index.x]] = true;
+            self.queue.push_back(index);
+        }
+    }
+}
+
+impl<'a, L, T> Slicer<'a, L, T> for ThickLine
+where
+    L: Layer,
+    T: 'a,
+{
+    type Output = &'a T;
+
+    type OutputMut = &'a mut T;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        data.get(
+            self.line
+                .map_meta
+                .wrap_index(self.index()?)
+                .as_array2_index(),
+        )
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        data.get_mut(
+            self.line
+                .map_meta
+                .wrap_index(self.index()?)
+                .as_array2_index(),
+        )
+    }
+
+    fn advance(&mut self) {
+        while self.queue.is_empty() && self.line.get_current_index().is_some() {
+            self.fill_queue();
+            self.line.step();
+        }
+
+        self.current = self.queue.pop_front();
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        self.current
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.line.current_map = Some(self.line.start_map);
+        self.visited.fill(false);
+        self.queue.clear();
+        self.current = None;
+        self.advance();
+    }
+}
+
+impl Polygon {
+    pub(crate) fn from_map<L: Layer, T>(
+        map_meta: CellMapMetadata,
+        vertices: &[Point2<f64>],
+    ) -> Result<Self, Error> {
+        if vertices.len() < 3 {
+            return Err(Error::PolygonTooFewVertices(vertices.len()));
+        }
+
+        let map_verts: Vec<Point2<f64>> = vertices
+            .iter()
+            .map(|v| map_meta.to_parent.inverse_transform_point(v))
+            .collect();
+
+        let n = map_verts.len();
+        let edges = (0..n)
+            .map(|i| (map_verts[i], map_verts[(i + 1) % n]))
+            .collect();
+
+        let mut slicer = Self {
+            bounds: map_meta.get_bounds(),
+            edges,
+            y: map_meta.get_bounds().y.0,
+            row_xs: Vec::new(),
+            row_idx: 0,
+            map_meta,
+        };
+        slicer.seek_first_row();
+
+        Ok(slicer)
+    }
+
+    /// Computes the sorted list of cell columns on row `y` whose centers lie inside the polygon.
+    fn compute_row_xs(&self, y: usize) -> Vec<usize> {
+        let y_scan = y as f64 + 0.5;
+
+        let mut crossings: Vec<f64> = self
+            .edges
+            .iter()
+            .filter_map(|&(p0, p1)| {
+                let y_min = p0.y.min(p1.y);
+                let y_max = p0.y.max(p1.y);
+
+                if y_min <= y_scan && y_scan < y_max {
+                    let t = (y_scan - p0.y) / (p1.y - p0.y);
+                    Some(p0.x + t * (p1.x - p0.x))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut xs = Vec::new();
+        for pair in crossings.chunks_exact(2) {
+            // A cell at column x is inside the span [pair[0], pair[1]) if its center x + 0.5 is,
+            // i.e. if x is in [pair[0] - 0.5, pair[1] - 0.5).
+            let x0 = ((pair[0] - 0.5).ceil() as isize)
+                .max(self.bounds.x.0 as isize)
+                .max(0);
+            let x1 = ((pair[1] - 0.5).ceil() as isize).min(self.bounds.x.1 as isize);
+
+            for x in x0..x1 {
+                xs.push(x as usize);
+            }
+        }
+
+        xs
+    }
+
+    /// Advances `self.y` (from its current value, inclusive) until a row with at least one
+    /// filled column is found, or the map's y bound is reached.
+    fn seek_first_row(&mut self) {
+        self.row_idx = 0;
+
+        while self.y < self.bounds.y.1 {
+            self.row_xs = self.compute_row_xs(self.y);
+
+            if !self.row_xs.is_empty() {
+                return;
+            }
+
+            self.y += 1;
+        }
+
+        self.row_xs = Vec::new();
+    }
+}
+
+impl<'a, L, T> Slicer<'a, L, T> for Polygon
+where
+    L: Layer,
+    T: 'a,
+{
+    type Output = &'a T;
+
+    type OutputMut = &'a mut T;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        data.get(self.map_meta.wrap_index(self.index()?).as_array2_index())
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        data.get_mut(self.map_meta.wrap_index(self.index()?).as_array2_index())
+    }
+
+    fn advance(&mut self) {
+        self.row_idx += 1;
+
+        if self.row_idx >= self.row_xs.len() {
+            self.y += 1;
+            self.seek_first_row();
+        }
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        if self.y < self.bounds.y.1 && self.row_idx < self.row_xs.len() {
+            Some(Point2::new(self.row_xs[self.row_idx], self.y))
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.y = self.bounds.y.0;
+        self.seek_first_row();
+    }
+}
+
+impl<F> Wavefront<F>
+where
+    F: Fn(Point2<usize>) -> Option<f64>,
+{
+    pub(crate) fn from_map<L: Layer, T>(
+        map_meta: CellMapMetadata,
+        seeds: Vec<Point2<usize>>,
+        connectivity: WavefrontConnectivity,
+        step_cost: F,
+    ) -> Self {
+        let bounds = map_meta.get_bounds();
+        let visited = Array2::from_elem((bounds.y.1, bounds.x.1), false);
+
+        let mut slicer = Self {
+            bounds,
+            connectivity,
+            step_cost,
+            seeds,
+            visited,
+            heap: std::collections::BinaryHeap::new(),
+            current: None,
+            map_meta,
+        };
+        slicer.reseed();
+        slicer.pop_next();
+
+        slicer
+    }
+
+    /// Clears the visited mask and heap, then pushes every in-bounds seed at cost `0.0`.
+    fn reseed(&mut self) {
+        self.visited.fill(false);
+        self.heap.clear();
+
+        for seed in &self.seeds {
+            if seed.in_bounds(&self.bounds) {
+                self.heap
+                    .push(std::cmp::Reverse((OrderedCost(0.0), seed.x, seed.y)));
+            }
+        }
+    }
+
+    /// The offsets of the neighbors expanded from a cell under the current
+    /// [`WavefrontConnectivity`].
+    fn neighbor_offsets(&self) -> &'static [(isize, isize)] {
+        match self.connectivity {
+            WavefrontConnectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            WavefrontConnectivity::Eight => &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ],
+        }
+    }
+
+    /// Pops the lowest-cost unvisited cell from the heap, marks it visited, pushes its unvisited
+    /// neighbors, and records it as `self.current` (`None` once the heap is drained).
+    fn pop_next(&mut self) {
+        while let Some(std::cmp::Reverse((OrderedCost(cost), x, y))) = self.heap.pop() {
+            if self.visited[[y, x]] {
+                continue;
+            }
+            self.visited[[y, x]] = true;
+
+            for (dx, dy) in self.neighbor_offsets() {
+                let nx = x as isize + dx;
+                let ny = y as isize + dy;
+                if nx < 0 || ny < 0 {
+                    continue;
+                }
+                let neighbor = Point2::new(nx as usize, ny as usize);
+
+                if !neighbor.in_bounds(&self.bounds) || self.visited[[neighbor.y, neighbor.x]] {
+                    continue;
+                }
+
+                if let Some(step) = (self.step_cost)(neighbor) {
+                    self.heap.push(std::cmp::Reverse((
+                        OrderedCost(cost + step),
+                        neighbor.x,
+                        neighbor.y,
+                    )));
+                }
+            }
+
+            self.current = Some((Point2::new(x, y), cost));
+            return;
+        }
+
+        self.current = None;
+    }
+}
+
+impl<'a, L, T, F> Slicer<'a, L, T> for Wavefront<F>
+where
+    L: Layer,
+    T: 'a,
+    F: Fn(Point2<usize>) -> Option<f64>,
+{
+    type Output = &'a T;
+
+    type OutputMut = &'a mut T;
+
+    fn slice(&self, data: &'a Array2<T>) -> Option<Self::Output> {
+        data.get(self.map_meta.wrap_index(self.index()?).as_array2_index())
+    }
+
+    fn slice_mut(&self, data: &'a mut Array2<T>) -> Option<Self::OutputMut> {
+        data.get_mut(self.map_meta.wrap_index(self.index()?).as_array2_index())
+    }
+
+    fn advance(&mut self) {
+        self.pop_next();
+    }
+
+    fn index(&self) -> Option<Point2<usize>> {
+        self.current.map(|(index, _)| index)
+    }
+
+    fn reset(&mut self, _layer: Option<L>) {
+        self.reseed();
+        self.pop_next();
+    }
+}
@@ -4,8 +4,10 @@
 // IMPORTS
 // ------------------------------------------------------------------------------------------------
 
+use std::collections::HashMap;
+
 use super::*;
-use crate::{test_utils::TestLayers, CellMapParams};
+use crate::{cell_map::Bounds, test_utils::TestLayers, CellMapParams};
 
 /// Check that iterator constructors return the right ok or error.
 #[test]
@@ -68,3 +70,132 @@ fn counts() -> Result<(), CellMapError> {
 
     Ok(())
 }
+
+/// Builds a map with distinct per-cell values and then shifts it with [`CellMap::move_by`], so
+/// tests can check that a slicer's view of a cell agrees with [`CellMap::get`] once the map's
+/// logical and physical indices have diverged.
+fn shifted_map() -> CellMap<TestLayers, f64> {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 6), (0, 6)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+        *v = (idx.x * 10 + idx.y) as f64;
+    }
+
+    map.move_by(Vector2::new(2, 0), -1.0);
+
+    map
+}
+
+/// Checks that every `(index, value)` pair yielded by `got` agrees with [`CellMap::get`], which
+/// is known to respect the map's ring-buffer wrapping.
+fn assert_agrees_with_get(map: &CellMap<TestLayers, f64>, got: HashMap<Point2<usize>, f64>) {
+    assert!(!got.is_empty());
+    for (idx, value) in got {
+        assert_eq!(
+            value,
+            *map.get(TestLayers::Layer0, idx).unwrap(),
+            "slicer returned a different value than CellMap::get at {:?}",
+            idx
+        );
+    }
+}
+
+/// Regression test for the ring-buffer wrapping bug in [`Disk`]: before the fix, `disk_iter`
+/// indexed the backing storage directly and so disagreed with [`CellMap::get`] once `move_by`
+/// had shifted the map's logical origin away from the physical origin.
+#[test]
+fn disk_iter_respects_ring_buffer_after_move_by() {
+    let map = shifted_map();
+
+    let centre = map.position(Point2::new(3, 3)).unwrap();
+    let via_disk: HashMap<Point2<usize>, f64> = map
+        .disk_iter(centre, 1.5, DiskMetric::Euclidean)
+        .layer(TestLayers::Layer0)
+        .indexed()
+        .map(|((_, idx), &v)| (idx, v))
+        .collect();
+
+    assert_agrees_with_get(&map, via_disk);
+}
+
+/// Regression test for the ring-buffer wrapping bug in [`Line`].
+#[test]
+fn line_iter_respects_ring_buffer_after_move_by() {
+    let map = shifted_map();
+
+    let start = map.position(Point2::new(0, 0)).unwrap();
+    let end = map.position(Point2::new(5, 5)).unwrap();
+    let via_line: HashMap<Point2<usize>, f64> = map
+        .line_iter(start, end)
+        .unwrap()
+        .layer(TestLayers::Layer0)
+        .indexed()
+        .map(|((_, idx), &v)| (idx, v))
+        .collect();
+
+    assert_agrees_with_get(&map, via_line);
+}
+
+/// Regression test for the ring-buffer wrapping bug in [`ThickLine`].
+#[test]
+fn thick_line_iter_respects_ring_buffer_after_move_by() {
+    let map = shifted_map();
+
+    let start = map.position(Point2::new(0, 0)).unwrap();
+    let end = map.position(Point2::new(5, 5)).unwrap();
+    let via_thick_line: HashMap<Point2<usize>, f64> = map
+        .thick_line_iter(start, end, 1.0)
+        .unwrap()
+        .layer(TestLayers::Layer0)
+        .indexed()
+        .map(|((_, idx), &v)| (idx, v))
+        .collect();
+
+    assert_agrees_with_get(&map, via_thick_line);
+}
+
+/// Regression test for the ring-buffer wrapping bug in [`Polygon`].
+#[test]
+fn polygon_iter_respects_ring_buffer_after_move_by() {
+    let map = shifted_map();
+
+    let vertices = [
+        map.position(Point2::new(1, 1)).unwrap(),
+        map.position(Point2::new(4, 1)).unwrap(),
+        map.position(Point2::new(4, 4)).unwrap(),
+        map.position(Point2::new(1, 4)).unwrap(),
+    ];
+    let via_polygon: HashMap<Point2<usize>, f64> = map
+        .polygon_iter(&vertices)
+        .unwrap()
+        .layer(TestLayers::Layer0)
+        .indexed()
+        .map(|((_, idx), &v)| (idx, v))
+        .collect();
+
+    assert_agrees_with_get(&map, via_polygon);
+}
+
+/// Regression test for the ring-buffer wrapping bug in [`Wavefront`].
+#[test]
+fn wavefront_iter_respects_ring_buffer_after_move_by() {
+    let map = shifted_map();
+
+    let via_wavefront: HashMap<Point2<usize>, f64> = map
+        .wavefront_iter(vec![Point2::new(3, 3)], WavefrontConnectivity::Four, |_| {
+            Some(1.0)
+        })
+        .layer(TestLayers::Layer0)
+        .indexed()
+        .map(|((_, idx), &v)| (idx, v))
+        .collect();
+
+    assert_agrees_with_get(&map, via_wavefront);
+}
@@ -16,7 +16,9 @@
 /// # Safety
 ///
 /// Do not manually implement this trait for non-enum types, as [`CellMap`] will be unable to
-/// guarentee that the layer you're attempting to access will be present in the map.
+/// guarentee that the layer you're attempting to access will be present in the map. The one
+/// exception is [`DynamicLayer`], which trades that compile-time guarantee for a layer set that
+/// can grow and shrink at runtime; see its documentation for the caveats that come with it.
 ///
 /// # Example
 /// ```
@@ -30,6 +32,7 @@
 /// ```
 ///
 /// [`CellMap`]: crate::CellMap
+/// [`DynamicLayer`]: crate::DynamicLayer
 pub trait Layer: Clone {
     /// Contains the total number of layers possible with this [`Layer`]
     const NUM_LAYERS: usize;
@@ -49,5 +52,66 @@ pub trait Layer: Clone {
     fn from_index(index: usize) -> Self;
 
     /// Returns a vector of all layers in index order.
+    ///
+    /// `#[derive(Layer)]` builds this from a fixed-size array constructed in a `const` context,
+    /// since the set of layers is known at compile time; it's converted to a `Vec` here only so
+    /// that runtime-sized [`Layer`] impls like [`DynamicLayer`] can implement this method too.
+    ///
+    /// [`DynamicLayer`]: crate::DynamicLayer
     fn all() -> Vec<Self>;
+
+    /// Maps a layer index into a variant of the layer, returning `None` instead of panicking if
+    /// the index doesn't match a layer.
+    ///
+    /// The default implementation just bounds-checks `index` against [`Layer::NUM_LAYERS`] and
+    /// defers to [`Layer::from_index`], which is always correct for the derived, fixed-size enum
+    /// layers [`Layer`] is designed around. [`DynamicLayer`] overrides this to consult its runtime
+    /// registry instead, since its [`Layer::NUM_LAYERS`] is always `0` and its indices can be
+    /// sparse.
+    ///
+    /// [`DynamicLayer`]: crate::DynamicLayer
+    fn try_from_index(index: usize) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if index < Self::NUM_LAYERS {
+            Some(Self::from_index(index))
+        } else {
+            None
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A [`Layer`] with exactly one variant, for [`CellMap`]s that only ever need a single layer.
+///
+/// This is mainly useful as the output layer set of operations that collapse a multi-layer
+/// [`CellMap`] down to one derived layer, e.g.
+/// [`CellMapIter::reduce_layers`](crate::iterators::CellMapIter::reduce_layers), where there's no
+/// meaningful caller-defined enum to index the (singular) result by.
+///
+/// Implemented by hand rather than with `#[derive(Layer)]`, the same exception made for
+/// [`DynamicLayer`](crate::DynamicLayer), since there's no enum with variants to derive from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OneLayer;
+
+impl Layer for OneLayer {
+    const NUM_LAYERS: usize = 1;
+    const FIRST: Self = OneLayer;
+
+    fn to_index(&self) -> usize {
+        0
+    }
+
+    fn from_index(index: usize) -> Self {
+        assert_eq!(index, 0, "OneLayer only has a single valid index, 0");
+        OneLayer
+    }
+
+    fn all() -> Vec<Self> {
+        vec![OneLayer]
+    }
 }
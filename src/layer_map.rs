@@ -0,0 +1,134 @@
+//! Provides [`LayerMap`], a dense container storing exactly one value per layer.
+//!
+//! Metadata that's logically "one entry per layer" — per-layer fill values, colormaps, units,
+//! staleness timestamps — is easy to end up carrying around in an ad-hoc `Vec` indexed by
+//! `L::to_index()`, whose length and ordering can silently desynchronize from the layer enum as
+//! layers are added or reordered. [`LayerMap<L, V>`] is the per-layer sibling of [`CellMap`]'s
+//! per-cell grids: it stores exactly one `V` per layer of `L`, indexed by [`Layer`] itself rather
+//! than a raw `usize`, so a missing or misordered entry is a compile-time or construction-time
+//! error instead of a silent desync.
+//!
+//! [`CellMap`]: crate::CellMap
+
+// ------------------------------------------------------------------------------------------------
+// IMPORTS
+// ------------------------------------------------------------------------------------------------
+
+use std::{
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::Layer;
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A dense container storing exactly one `V` per layer of `L`, indexed by [`Layer::to_index()`].
+///
+/// [`Layer::to_index()`]: crate::Layer::to_index
+#[derive(Debug, Clone)]
+pub struct LayerMap<L, V>
+where
+    L: Layer,
+{
+    data: Vec<V>,
+    layer_type: PhantomData<L>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// IMPLS
+// ------------------------------------------------------------------------------------------------
+
+impl<L, V> LayerMap<L, V>
+where
+    L: Layer,
+{
+    /// Creates a new [`LayerMap`] by calling `f` once for each layer, in index order.
+    pub fn new_from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(L) -> V,
+    {
+        Self {
+            data: L::all().into_iter().map(&mut f).collect(),
+            layer_type: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over every layer and its value, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (L, &V)> {
+        L::all().into_iter().zip(self.data.iter())
+    }
+}
+
+impl<L, V> LayerMap<L, V>
+where
+    L: Layer,
+    V: Clone,
+{
+    /// Creates a new [`LayerMap`], filling every layer with a clone of `elem`.
+    pub fn new_from_elem(elem: V) -> Self {
+        Self {
+            data: vec![elem; L::NUM_LAYERS],
+            layer_type: PhantomData,
+        }
+    }
+}
+
+impl<L, V> Index<L> for LayerMap<L, V>
+where
+    L: Layer,
+{
+    type Output = V;
+
+    fn index(&self, index: L) -> &Self::Output {
+        &self.data[index.to_index()]
+    }
+}
+
+impl<L, V> IndexMut<L> for LayerMap<L, V>
+where
+    L: Layer,
+{
+    fn index_mut(&mut self, index: L) -> &mut Self::Output {
+        &mut self.data[index.to_index()]
+    }
+}
+
+impl<L, V> FromIterator<(L, V)> for LayerMap<L, V>
+where
+    L: Layer,
+{
+    /// Builds a [`LayerMap`] from `(layer, value)` pairs, which may arrive in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iter` yields no value for some layer in `L::all()`. If a layer is yielded more
+    /// than once, the last value wins.
+    fn from_iter<I: IntoIterator<Item = (L, V)>>(iter: I) -> Self {
+        let mut slots: Vec<Option<V>> = (0..L::NUM_LAYERS).map(|_| None).collect();
+
+        for (layer, value) in iter {
+            let index = layer.to_index();
+            if index >= slots.len() {
+                slots.resize_with(index + 1, || None);
+            }
+            slots[index] = Some(value);
+        }
+
+        let data = slots
+            .into_iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                slot.unwrap_or_else(|| panic!("LayerMap::from_iter got no value for layer index {}", index))
+            })
+            .collect();
+
+        Self {
+            data,
+            layer_type: PhantomData,
+        }
+    }
+}
@@ -129,11 +129,15 @@ mod macros;
 
 pub(crate) mod cell_map;
 pub mod cell_map_file;
+pub mod chunked;
+pub mod dynamic_layer;
 pub mod error;
 pub(crate) mod extensions;
 pub mod iterators;
 mod layer;
+mod layer_map;
 mod map_metadata;
+pub mod resolution_tree;
 #[cfg(test)]
 mod tests;
 
@@ -141,10 +145,15 @@ mod tests;
 // EXPORTS
 // ------------------------------------------------------------------------------------------------
 
-pub use crate::cell_map::{Bounds, CellMap, CellMapParams};
+pub use crate::cell_map::{Bounds, Bounds3, CellMap, CellMapParams, RayCellIter};
 pub use cell_map_macro::Layer;
+pub use chunked::ChunkedLayer;
+pub use dynamic_layer::DynamicLayer;
 pub use error::Error;
-pub use layer::Layer;
+pub use layer::{Layer, OneLayer};
+pub use layer_map::LayerMap;
+pub use map_metadata::{BresenhamLineIter, LineTraversal};
+pub use resolution_tree::ResolutionTree;
 
 // ------------------------------------------------------------------------------------------------
 // USEFUL TEST UTILITIES
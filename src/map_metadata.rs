@@ -7,7 +7,9 @@
 // IMPORTS
 // ------------------------------------------------------------------------------------------------
 
-use nalgebra::{Affine2, Isometry2, Matrix3, Point2, Vector2};
+use std::collections::VecDeque;
+
+use nalgebra::{Affine2, Isometry2, Matrix3, Point2, Vector2, Vector3};
 use serde::{Deserialize, Serialize};
 
 use crate::{iterators::slicers::RectBounds, CellMapParams};
@@ -25,6 +27,12 @@ pub(crate) struct CellMapMetadata {
     /// The size (resolution) of each cell in the map, in both the `x` and `y` directions.
     pub cell_size: Vector2<f64>,
 
+    /// The size (resolution) of each cell in the `z` direction, for volumetric maps built from
+    /// stacked layers (see [`Bounds3`](crate::cell_map::Bounds3)).
+    ///
+    /// Defaults to `1.0` for purely 2D maps, where it goes unused.
+    pub cell_size_z: f64,
+
     /// The number of cells in the `x` and `y` directions.
     pub num_cells: Vector2<usize>,
 
@@ -47,6 +55,20 @@ pub(crate) struct CellMapMetadata {
     /// The transform between the map's frame and the parent frame. This is the transform that will
     /// be applied when going from a cell index to a parent-frame position.
     pub to_parent: Affine2<f64>,
+
+    /// The physical storage offset of the logical cell `(0, 0)`, used to implement the map as a
+    /// per-axis circular buffer.
+    ///
+    /// Logical cell indices (the ones seen by users of [`CellMap`]) are mapped to physical
+    /// `ndarray` storage indices via `(logical + start_index) % num_cells`. This allows
+    /// [`CellMap::move_by`] and [`CellMap::move_to`] to "scroll" the map over a moving vehicle
+    /// without having to reallocate and copy the whole map, since only the rows/columns that
+    /// wrap around need to be overwritten.
+    ///
+    /// [`CellMap`]: crate::CellMap
+    /// [`CellMap::move_by`]: crate::CellMap::move_by
+    /// [`CellMap::move_to`]: crate::CellMap::move_to
+    pub start_index: Vector2<usize>,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -54,6 +76,12 @@ pub(crate) struct CellMapMetadata {
 // ------------------------------------------------------------------------------------------------
 
 impl CellMapMetadata {
+    /// Returns the cell size in all three dimensions, using [`Self::cell_size_z`] for the `z`
+    /// axis.
+    pub fn cell_size_3d(&self) -> Vector3<f64> {
+        Vector3::new(self.cell_size.x, self.cell_size.y, self.cell_size_z)
+    }
+
     /// Returns the bounds of the map in map frame coordinates.
     pub fn get_bounds(&self) -> RectBounds {
         Vector2::new((0, self.num_cells.x), (0, self.num_cells.y))
@@ -64,6 +92,47 @@ impl CellMapMetadata {
         index.x < self.num_cells.x && index.y < self.num_cells.y
     }
 
+    /// Maps a logical cell index (the one seen by users of [`CellMap`]) to the physical `ndarray`
+    /// storage index that it's currently stored at, accounting for [`Self::start_index`].
+    ///
+    /// This does not check that `index` is inside the map.
+    ///
+    /// [`CellMap`]: crate::CellMap
+    pub fn wrap_index(&self, index: Point2<usize>) -> Point2<usize> {
+        Point2::new(
+            (index.x + self.start_index.x) % self.num_cells.x,
+            (index.y + self.start_index.y) % self.num_cells.y,
+        )
+    }
+
+    /// Builds the `to_parent` affine transform for the given map placement and cell size.
+    pub(crate) fn calc_to_parent(
+        position_in_parent: Vector2<f64>,
+        rotation_in_parent_rad: f64,
+        cell_size: Vector2<f64>,
+    ) -> Affine2<f64> {
+        // First build isometry to convert from the parent to map
+        let isom_from_parent = Isometry2::new(position_in_parent, rotation_in_parent_rad);
+
+        // Scale transformation matrix, based on cell size.
+        let scale = Matrix3::new(
+            cell_size.x,
+            0.0,
+            0.0,
+            0.0,
+            cell_size.y,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        );
+
+        // Build the affine by multiplying isom and scale, which will take the translation and
+        // rotation of isom and scale it by the cell size. Scale must come first so that the isom,
+        // which is in parent coordinates, is not scaled itself.
+        Affine2::from_matrix_unchecked(isom_from_parent.to_matrix() * scale)
+    }
+
     /// Returns the position in the parent frame of the centre of the given cell index.
     ///
     /// Returns `None` if the given `index` is not inside the map.
@@ -134,38 +203,164 @@ impl CellMapMetadata {
             .collect();
         Point2::new(els[0], els[1])
     }
+
+    /// Returns an iterator over the cell indices crossed by the straight segment from
+    /// `start_parent` to `end_parent` (positions in the parent frame), stepped with the integer
+    /// Bresenham algorithm in cell-index space.
+    ///
+    /// `traversal` selects whether a diagonal step also emits the two corner cells the segment
+    /// clips on its way to the diagonal neighbour ([`LineTraversal::Supercover`]), or skips them
+    /// as in plain Bresenham ([`LineTraversal::Bresenham`]). The iterator clips to the map via
+    /// [`Self::is_in_map`] and stops as soon as a cell falls outside it (yielding nothing at all
+    /// if `start_parent` is already outside), which is what lets callers use it directly for
+    /// occupancy-grid ray casting: walk the free cells along a beam, then mark its endpoint
+    /// occupied.
+    pub fn line_cells(
+        &self,
+        start_parent: Point2<f64>,
+        end_parent: Point2<f64>,
+        traversal: LineTraversal,
+    ) -> BresenhamLineIter {
+        let start = unsafe { self.index_unchecked(start_parent) };
+        let end = unsafe { self.index_unchecked(end_parent) };
+
+        let dx = (end.x - start.x).abs();
+        let dy = (end.y - start.y).abs();
+        let sx = if start.x < end.x { 1 } else { -1 };
+        let sy = if start.y < end.y { 1 } else { -1 };
+
+        BresenhamLineIter {
+            metadata: *self,
+            traversal,
+            x: start.x,
+            y: start.y,
+            end_x: end.x,
+            end_y: end.y,
+            dx,
+            dy,
+            sx,
+            sy,
+            err: dx - dy,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Controls whether [`CellMapMetadata::line_cells`] also emits the cells a diagonal step clips,
+/// in addition to the cells plain Bresenham stepping visits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineTraversal {
+    /// Only the cells the standard Bresenham step sequence visits.
+    Bresenham,
+
+    /// Also emits, at each diagonal step, the two cells the segment clips on its way to the
+    /// diagonal neighbour, so no cell it physically passes through is skipped.
+    Supercover,
+}
+
+/// Iterator over the cell indices a straight segment between two parent-frame positions crosses,
+/// produced by [`CellMapMetadata::line_cells`].
+///
+/// [`CellMap`]: crate::CellMap
+#[derive(Debug, Clone)]
+pub struct BresenhamLineIter {
+    metadata: CellMapMetadata,
+    traversal: LineTraversal,
+    x: isize,
+    y: isize,
+    end_x: isize,
+    end_y: isize,
+    dx: isize,
+    dy: isize,
+    sx: isize,
+    sy: isize,
+    err: isize,
+    pending: VecDeque<Point2<usize>>,
+    done: bool,
+}
+
+impl BresenhamLineIter {
+    fn checked_index(&self, x: isize, y: isize) -> Option<Point2<usize>> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        let index = Point2::new(x as usize, y as usize);
+
+        if self.metadata.is_in_map(index) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for BresenhamLineIter {
+    type Item = Point2<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(cell) = self.pending.pop_front() {
+            return Some(cell);
+        }
+
+        if self.done {
+            return None;
+        }
+
+        let current = self.checked_index(self.x, self.y);
+
+        if current.is_none() {
+            self.done = true;
+            return None;
+        }
+
+        if self.x == self.end_x && self.y == self.end_y {
+            self.done = true;
+            return current;
+        }
+
+        let e2 = 2 * self.err;
+        let step_x = e2 > -self.dy;
+        let step_y = e2 < self.dx;
+
+        if self.traversal == LineTraversal::Supercover && step_x && step_y {
+            if let Some(corner) = self.checked_index(self.x + self.sx, self.y) {
+                self.pending.push_back(corner);
+            }
+            if let Some(corner) = self.checked_index(self.x, self.y + self.sy) {
+                self.pending.push_back(corner);
+            }
+        }
+
+        if step_x {
+            self.err -= self.dy;
+            self.x += self.sx;
+        }
+        if step_y {
+            self.err += self.dx;
+            self.y += self.sy;
+        }
+
+        current
+    }
 }
 
 impl From<CellMapParams> for CellMapMetadata {
     fn from(params: CellMapParams) -> Self {
-        // First build isometry to convert from the parent to map
-        let isom_from_parent =
-            Isometry2::new(params.position_in_parent, params.rotation_in_parent_rad);
-
-        // Scale transformation matrix, based on cell size.
-        let scale = Matrix3::new(
-            params.cell_size.x,
-            0.0,
-            0.0,
-            0.0,
-            params.cell_size.y,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
+        let to_parent = Self::calc_to_parent(
+            params.position_in_parent,
+            params.rotation_in_parent_rad,
+            params.cell_size,
         );
 
-        // Build the affine by multiplying isom and scale, which will take the translation and
-        // rotation of isom and scale it by the cell size. Scale must come first so that the isom,
-        // which is in parent coordinates, is not scaled itself. Get the inverse of
-        // isom_from_parent to get the to_parent
-        let to_parent = Affine2::from_matrix_unchecked(isom_from_parent.to_matrix() * scale);
-
         Self {
             cell_size: params.cell_size,
-            num_cells: params.num_cells,
+            cell_size_z: 1.0,
+            num_cells: params.cell_bounds.get_num_cells(),
             cell_boundary_precision: params.cell_boundary_precision,
             to_parent,
+            start_index: Vector2::zeros(),
         }
     }
 }
@@ -0,0 +1,159 @@
+//! Hierarchical multi-resolution [`Bounds`], built by repeated quadtree subdivision.
+//!
+//! Inspired by the resolution-hierarchy idea in H3's hexagonal grid system: a [`ResolutionTree`]
+//! lets a region be represented at several resolutions at once, from a single coarse root bounds
+//! down to many small, fine bounds. Unlike H3's hexagons, [`Bounds::subdivide`] splits a bounds
+//! into quadrants of the *same coordinate space* rather than rescaling indices, so a cell's
+//! coordinates don't change as you move between resolution levels - only which bounds contains it
+//! does. This is what lets uniform regions of a [`CellMap`] (all-free, or all-unknown) collapse
+//! into a single coarse node via [`ResolutionTree::compact`], re-subdividing only once a sensor
+//! update introduces detail there.
+//!
+//! [`CellMap`]: crate::CellMap
+
+// ------------------------------------------------------------------------------------------------
+// IMPORTS
+// ------------------------------------------------------------------------------------------------
+
+use nalgebra::Point2;
+
+use crate::cell_map::Bounds;
+
+// ------------------------------------------------------------------------------------------------
+// FUNCTIONS
+// ------------------------------------------------------------------------------------------------
+
+/// Number of leaf cells a single bounds `depth` levels above the finest resolution subdivides
+/// into, i.e. `4^depth`.
+///
+/// Analogous to H3's `HEXAGON_CHILDREN_COUNTS` table, just for a quadtree rather than a hexagonal
+/// grid.
+pub fn children_count(depth: u32) -> u64 {
+    4u64.saturating_pow(depth)
+}
+
+// ------------------------------------------------------------------------------------------------
+// STRUCTS
+// ------------------------------------------------------------------------------------------------
+
+/// A hierarchy of [`Bounds`], built by repeatedly calling [`Bounds::subdivide`] on a root bounds.
+///
+/// Resolution `0` is the coarsest level (just the root bounds); each increasing resolution level
+/// holds the quadrant children of every bounds at the level below it. The invariant maintained
+/// throughout is the one [`Bounds::subdivide`] guarantees: every child is fully contained in its
+/// parent, and the union of a bounds' four children always equals the parent exactly, so
+/// [`Bounds::get_index`]/[`Bounds::get_slice_of_other`] stay consistent no matter which level
+/// they're used at.
+#[derive(Debug, Clone)]
+pub struct ResolutionTree {
+    /// `levels[r]` holds every [`Bounds`] at resolution `r`, coarsest (the root) first.
+    levels: Vec<Vec<Bounds>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// IMPLS
+// ------------------------------------------------------------------------------------------------
+
+impl ResolutionTree {
+    /// Builds a tree of `max_resolution + 1` levels (resolutions `0..=max_resolution`) by
+    /// repeatedly subdividing `root`.
+    pub fn new(root: Bounds, max_resolution: u32) -> Self {
+        let mut levels = vec![vec![root]];
+
+        for _ in 0..max_resolution {
+            let children = levels
+                .last()
+                .unwrap()
+                .iter()
+                .flat_map(Bounds::subdivide)
+                .collect();
+            levels.push(children);
+        }
+
+        Self { levels }
+    }
+
+    /// Returns the root bounds, at resolution `0`.
+    pub fn root(&self) -> Bounds {
+        self.levels[0][0]
+    }
+
+    /// Returns the finest resolution level this tree was built to.
+    pub fn max_resolution(&self) -> u32 {
+        (self.levels.len() - 1) as u32
+    }
+
+    /// Returns every [`Bounds`] at resolution `r`, or `None` if `r` is beyond
+    /// [`ResolutionTree::max_resolution`].
+    pub fn level(&self, r: u32) -> Option<&[Bounds]> {
+        self.levels.get(r as usize).map(Vec::as_slice)
+    }
+
+    /// Returns the number of finest-resolution leaf cells a bounds at resolution `r` is made up
+    /// of, i.e. [`children_count`]`(max_resolution() - r)`.
+    pub fn leaf_count_at(&self, r: u32) -> u64 {
+        children_count(self.max_resolution().saturating_sub(r))
+    }
+
+    /// Finds the bounds at resolution `target_r` that contains `cell`.
+    ///
+    /// Since [`Bounds::subdivide`] splits a bounds into quadrants of the same coordinate space,
+    /// `cell`'s coordinates are the same at every resolution level - only which bounds contains it
+    /// changes - so walking from a cell at a fine resolution up to its parent at a coarser one is
+    /// just a search of the coarser level. Returns `None` if `target_r` is out of range, or `cell`
+    /// isn't covered by any bounds at that level.
+    pub fn parent_at(&self, cell: Point2<isize>, target_r: u32) -> Option<Bounds> {
+        self.level(target_r)?.iter().copied().find(|b| b.contains(cell))
+    }
+
+    /// Compacts a set of bounds at resolution `from_r` into the smallest set of coarser bounds
+    /// that covers exactly the same area, by repeatedly merging groups of four sibling bounds
+    /// whose union fully covers their shared parent.
+    ///
+    /// Analogous to H3's compaction of a set of fine cells into fewer, coarser ones: a uniform
+    /// region that happens to cover a whole parent bounds collapses into that single coarser
+    /// bounds, rather than staying represented as four (or more) finer ones. Entries that can't be
+    /// merged any further are returned unchanged, paired with the resolution level they ended up
+    /// at.
+    pub fn compact(&self, covered: &[Bounds], from_r: u32) -> Vec<(u32, Bounds)> {
+        let mut current: Vec<(u32, Bounds)> = covered.iter().map(|&b| (from_r, b)).collect();
+        let mut r = from_r;
+
+        while r > 0 {
+            let parents = match self.level(r - 1) {
+                Some(parents) => parents,
+                None => break,
+            };
+
+            let at_r: Vec<Bounds> = current
+                .iter()
+                .filter(|(level, _)| *level == r)
+                .map(|(_, b)| *b)
+                .collect();
+
+            let merged_parents: Vec<Bounds> = parents
+                .iter()
+                .copied()
+                .filter(|parent| parent.subdivide().iter().all(|child| at_r.contains(child)))
+                .collect();
+
+            if merged_parents.is_empty() {
+                break;
+            }
+
+            // Drop the children that got merged into a parent this round, then add the parents
+            // themselves at the next-coarsest level.
+            current.retain(|(level, b)| {
+                *level != r
+                    || !merged_parents
+                        .iter()
+                        .any(|parent| parent.subdivide().contains(b))
+            });
+            current.extend(merged_parents.into_iter().map(|parent| (r - 1, parent)));
+
+            r -= 1;
+        }
+
+        current
+    }
+}
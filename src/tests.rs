@@ -5,9 +5,15 @@
 // ------------------------------------------------------------------------------------------------
 
 use nalgebra::{Point2, Vector2};
+use ndarray::Array2;
 
 use super::*;
-use crate::{cell_map::Bounds, test_utils::TestLayers};
+use crate::{
+    cell_map::{BorderPolicy, Bounds, WindowMapConfig},
+    iterators::slicers::WindowPadding,
+    test_utils::TestLayers,
+    Error,
+};
 
 // ------------------------------------------------------------------------------------------------
 // TESTS
@@ -254,3 +260,449 @@ fn test_merge() {
     }
     println!();
 }
+
+#[test]
+fn test_inflate_obstacles_chamfer_distances() {
+    // 7x7 map, radius 3, with a single occupied cell right in the centre so the padded bounding
+    // box (centre +/- radius) exactly covers the whole map, and every chamfer distance in it can
+    // be hand-computed from the 3/4 step weights.
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 7), (0, 7)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+    map.set(TestLayers::Layer0, Point2::new(3, 3), 1.0).unwrap();
+
+    // Identity decay so the cost each cell ends up with is the approximate distance itself.
+    map.inflate_obstacles_chamfer(TestLayers::Layer0, TestLayers::Layer1, 3, |d| d);
+
+    let expect_dist = |index: Point2<usize>, expected: f64| {
+        let got = *map.get(TestLayers::Layer1, index).unwrap();
+        assert!(
+            (got - expected).abs() < 1e-9,
+            "distance at {:?}: expected {}, got {}",
+            index,
+            expected,
+            got
+        );
+    };
+
+    // The occupied cell itself.
+    expect_dist(Point2::new(3, 3), 0.0);
+    // Orthogonal neighbour: one 3-weighted step.
+    expect_dist(Point2::new(4, 3), 1.0);
+    expect_dist(Point2::new(2, 3), 1.0);
+    // Diagonal neighbour: one 4-weighted step.
+    expect_dist(Point2::new(4, 4), 4.0 / 3.0);
+    // Two orthogonal steps.
+    expect_dist(Point2::new(5, 3), 2.0);
+    // A diagonal step followed by an orthogonal step beats three orthogonal steps.
+    expect_dist(Point2::new(5, 4), 7.0 / 3.0);
+}
+
+#[test]
+fn test_inflate_obstacles_chamfer_no_occupied_cells_untouched() {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 5), (0, 5)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+    // Sentinel value that `inflate_obstacles_chamfer` should never overwrite, since `Layer0` (the
+    // `occupied` layer) is all zero.
+    map.iter_mut()
+        .layer(TestLayers::Layer1)
+        .for_each(|v| *v = -1.0);
+
+    map.inflate_obstacles_chamfer(TestLayers::Layer0, TestLayers::Layer1, 2, |d| d);
+
+    assert!(map.iter().layer(TestLayers::Layer1).all(|&v| v == -1.0));
+}
+
+#[test]
+fn test_inflate_obstacles_chamfer_clips_to_map_bounds() {
+    // Occupied cell in the top-left corner: the radius-padded bounding box would extend outside
+    // the map on two sides, which must be clipped rather than panicking or indexing out of
+    // bounds.
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 5), (0, 5)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+    map.set(TestLayers::Layer0, Point2::new(0, 0), 1.0).unwrap();
+
+    map.inflate_obstacles_chamfer(TestLayers::Layer0, TestLayers::Layer1, 2, |d| d);
+
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(0, 0)).unwrap(),
+        0.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(1, 0)).unwrap(),
+        1.0
+    );
+    // Outside the radius, so left at its initial value.
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(4, 4)).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn test_inflate_obstacles_chamfer_after_move_by() {
+    // Regression test: once the map's ring buffer has been scrolled, logical neighbours of the
+    // occupied cell are no longer physically adjacent in storage, so the chamfer pass must look
+    // them up through the wrapped logical index rather than raw physical offsets.
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 5), (0, 5)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    map.move_by(Vector2::new(2, 3), 0.0);
+
+    map.set(TestLayers::Layer0, Point2::new(2, 2), 1.0).unwrap();
+
+    map.inflate_obstacles_chamfer(TestLayers::Layer0, TestLayers::Layer1, 1, |_| 1.0);
+
+    // Logical orthogonal neighbours of the occupied cell should be inflated...
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(1, 2)).unwrap(),
+        1.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(3, 2)).unwrap(),
+        1.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(2, 1)).unwrap(),
+        1.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(2, 3)).unwrap(),
+        1.0
+    );
+    // ...while a cell far enough away to be outside the radius is left untouched.
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(0, 0)).unwrap(),
+        0.0
+    );
+}
+
+#[test]
+fn test_move_by_shifts_logical_cells_and_fills_leading_edge() {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 4), (0, 4)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+        *v = (idx.x * 10 + idx.y) as f64;
+    }
+
+    map.move_by(Vector2::new(1, 0), -1.0);
+
+    // Every cell except the leading edge (highest x, since we moved in +x) now holds
+    // the value that used to be one cell further in +x.
+    for x in 0..3 {
+        for y in 0..4 {
+            assert_eq!(
+                *map.get(TestLayers::Layer0, Point2::new(x, y)).unwrap(),
+                ((x + 1) * 10 + y) as f64
+            );
+        }
+    }
+
+    // The newly-exposed column is filled rather than keeping stale data.
+    for y in 0..4 {
+        assert_eq!(
+            *map.get(TestLayers::Layer0, Point2::new(3, y)).unwrap(),
+            -1.0
+        );
+    }
+}
+
+#[test]
+fn test_move_to_same_centre_is_a_no_op() {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 4), (0, 4)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+        *v = (idx.x * 10 + idx.y) as f64;
+    }
+
+    // With the default identity `to_parent` (no rotation/translation, unit cell size), the map's
+    // current centre is exactly `num_cells / 2` in parent-frame units.
+    map.move_to(Point2::new(2.0, 2.0), -1.0);
+
+    for x in 0..4 {
+        for y in 0..4 {
+            assert_eq!(
+                *map.get(TestLayers::Layer0, Point2::new(x, y)).unwrap(),
+                (x * 10 + y) as f64
+            );
+        }
+    }
+}
+
+/// Builds a 3x3 map whose `Layer0` cell values are `y * 3 + x + 1`, i.e. 1..=9 in raster order, so
+/// window border-handling tests have distinct values to tell clamped/padded/defaulted cells apart.
+fn window_test_map() -> CellMap<TestLayers, f64> {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 3), (0, 3)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+        *v = (idx.y * 3 + idx.x + 1) as f64;
+    }
+
+    map
+}
+
+/// As [`window_test_map`], but scrolled by [`CellMap::move_by`] before its values are set, so its
+/// logical `1..=9` raster-order layout is backed by a non-trivially wrapped physical layout --
+/// the state window-handling regression tests need to catch bugs that only show up once
+/// `start_index` is non-zero.
+///
+/// Scrolling before setting values (rather than after) keeps the expected values at each logical
+/// index the simple `y * 3 + x + 1` formula, rather than whatever [`CellMap::move_by`] reshuffled.
+fn window_test_map_after_move_by() -> CellMap<TestLayers, f64> {
+    let mut map = CellMap::<TestLayers, f64>::new_from_elem(
+        CellMapParams {
+            cell_bounds: Bounds::new((0, 3), (0, 3)).unwrap(),
+            cell_size: Vector2::new(1.0, 1.0),
+            ..Default::default()
+        },
+        0.0,
+    );
+
+    map.move_by(Vector2::new(1, 2), 0.0);
+
+    for ((_, idx), v) in map.iter_mut().indexed().layer(TestLayers::Layer0) {
+        *v = (idx.y * 3 + idx.x + 1) as f64;
+    }
+
+    map
+}
+
+#[test]
+fn test_map_windows_clamp_to_edge_vs_default_border() {
+    let mut map = window_test_map();
+    let sum = |window: &Array2<f64>| window.sum();
+
+    map.map_windows(
+        TestLayers::Layer0,
+        TestLayers::Layer1,
+        Vector2::new(1, 1),
+        BorderPolicy::ClampToEdge,
+        sum,
+    );
+    map.map_windows(
+        TestLayers::Layer0,
+        TestLayers::Layer2,
+        Vector2::new(1, 1),
+        BorderPolicy::Default,
+        sum,
+    );
+
+    // At the corner, the clamped border repeats the nearest in-map cell for every out-of-bounds
+    // window slot, while the default border treats them as zero.
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(0, 0)).unwrap(),
+        21.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer2, Point2::new(0, 0)).unwrap(),
+        12.0
+    );
+
+    // At the centre, the whole window is in bounds, so both border policies agree.
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer2, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+}
+
+#[test]
+fn test_window_map_clamp_replicates_edge_cells() {
+    let map = window_test_map();
+
+    let out = map
+        .window_map(Vector2::new(1, 1), WindowMapConfig::Clamp, |window| {
+            window.iter().sum::<f64>()
+        })
+        .unwrap();
+
+    assert_eq!(out.num_cells(), map.num_cells());
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+        21.0
+    );
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+}
+
+#[test]
+fn test_window_map_pad_fills_border_with_given_value() {
+    let map = window_test_map();
+
+    let out = map
+        .window_map(Vector2::new(1, 1), WindowMapConfig::Pad(99.0), |window| {
+            window.iter().sum::<f64>()
+        })
+        .unwrap();
+
+    assert_eq!(out.num_cells(), map.num_cells());
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+    for (x, y) in [
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (0, 1),
+        (2, 1),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+    ] {
+        assert_eq!(
+            *out.get(TestLayers::Layer0, Point2::new(x, y)).unwrap(),
+            99.0,
+            "border cell ({}, {}) should keep the pad value",
+            x,
+            y
+        );
+    }
+}
+
+#[test]
+fn test_window_map_shrink_crops_to_interior() {
+    let map = window_test_map();
+
+    let out = map
+        .window_map(Vector2::new(1, 1), WindowMapConfig::Shrink, |window| {
+            window.iter().sum::<f64>()
+        })
+        .unwrap();
+
+    assert_eq!(out.num_cells(), Vector2::new(1, 1));
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+        45.0
+    );
+}
+
+#[test]
+fn test_map_windows_clamp_to_edge_after_move_by() {
+    // Regression test: once the map's ring buffer has been scrolled, logical window slots are no
+    // longer physically adjacent in storage, so map_windows must gather them through the wrapped
+    // logical index rather than raw physical offsets.
+    let mut map = window_test_map_after_move_by();
+
+    let sum = |window: &Array2<f64>| window.sum();
+
+    map.map_windows(
+        TestLayers::Layer0,
+        TestLayers::Layer1,
+        Vector2::new(1, 1),
+        BorderPolicy::ClampToEdge,
+        sum,
+    );
+
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(0, 0)).unwrap(),
+        21.0
+    );
+    assert_eq!(
+        *map.get(TestLayers::Layer1, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+}
+
+#[test]
+fn test_window_map_clamp_after_move_by() {
+    // Regression test: the `Clamp` branch of `window_map` hand-rolls its own window gathering, so
+    // it needs its own proof it still respects the ring buffer once the map has been scrolled.
+    let map = window_test_map_after_move_by();
+
+    let out = map
+        .window_map(Vector2::new(1, 1), WindowMapConfig::Clamp, |window| {
+            window.iter().sum::<f64>()
+        })
+        .unwrap();
+
+    assert_eq!(out.num_cells(), map.num_cells());
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(0, 0)).unwrap(),
+        21.0
+    );
+    assert_eq!(
+        *out.get(TestLayers::Layer0, Point2::new(1, 1)).unwrap(),
+        45.0
+    );
+}
+
+#[test]
+fn test_window_iter_rejects_a_scrolled_map() {
+    // Regression test: `Windows` returns real write-through `ArrayViewMut2`s, so it can't stitch
+    // together a window straddling the ring buffer's wrap point. Rather than silently returning
+    // wrongly-wrapped data, construction must fail once the map has been scrolled.
+    let map = window_test_map_after_move_by();
+
+    assert!(matches!(
+        map.window_iter(Vector2::new(1, 1)),
+        Err(Error::WindowedIterOnScrolledMap(_))
+    ));
+}
+
+#[test]
+fn test_padded_window_iter_after_move_by() {
+    // Regression test: unlike `Windows`, every `PaddedWindows` window is already an owned copy, so
+    // it's expected to keep giving correct answers once the map has been scrolled.
+    let map = window_test_map_after_move_by();
+
+    let window = map
+        .padded_window_iter(Vector2::new(1, 1), WindowPadding::Clamp)
+        .unwrap()
+        .indexed()
+        .layer(TestLayers::Layer0)
+        .find(|((_, idx), _)| *idx == Point2::new(0, 0))
+        .unwrap()
+        .1;
+
+    assert_eq!(window.sum(), 21.0);
+}